@@ -0,0 +1,89 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Emitter, Manager, State};
+
+use crate::clipboard_snapshot::ClipboardSnapshot;
+
+/// Whether clipboard history is being recorded, as shown by the tray icon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureStatus {
+    Recording,
+    /// The user paused capture.
+    Paused,
+    /// Nabu is holding the clipboard while it pastes and will restore it, so
+    /// changes aren't recorded.
+    Locked,
+    /// The clipboard watcher failed.
+    Error(String),
+}
+
+pub struct CaptureState {
+    paused: AtomicBool,
+    error: Mutex<Option<String>>,
+}
+
+pub fn init_capture_state() -> CaptureState {
+    CaptureState {
+        paused: AtomicBool::new(false),
+        error: Mutex::new(None),
+    }
+}
+
+impl CaptureState {
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+}
+
+pub fn capture_status(app_handle: &AppHandle) -> CaptureStatus {
+    let state = app_handle.state::<CaptureState>();
+    if let Some(error) = state.error.lock().unwrap().clone() {
+        return CaptureStatus::Error(error);
+    }
+    if state.is_paused() {
+        return CaptureStatus::Paused;
+    }
+    if app_handle
+        .state::<ClipboardSnapshot>()
+        .is_suppressing_capture()
+    {
+        return CaptureStatus::Locked;
+    }
+    CaptureStatus::Recording
+}
+
+/// Pauses or resumes capture, and tells the tray and the panel.
+pub fn set_paused(app_handle: &AppHandle, paused: bool) {
+    app_handle
+        .state::<CaptureState>()
+        .paused
+        .store(paused, Ordering::SeqCst);
+
+    crate::tray::refresh_menu(app_handle);
+    crate::tray::update_status(app_handle);
+    let _ = app_handle.emit("capture-paused-changed", paused);
+}
+
+#[command]
+pub fn set_capture_paused(app: AppHandle, paused: bool) {
+    set_paused(&app, paused);
+}
+
+#[command]
+pub fn is_capture_paused(state: State<CaptureState>) -> bool {
+    state.is_paused()
+}
+
+/// Shows why recording failed in the tray, or clears the error with `None`.
+pub fn set_capture_error(app_handle: &AppHandle, error: Option<String>) {
+    let state = app_handle.state::<CaptureState>();
+    let changed = {
+        let mut current = state.error.lock().unwrap();
+        let changed = *current != error;
+        *current = error;
+        changed
+    };
+    if changed {
+        crate::tray::update_status(app_handle);
+    }
+}
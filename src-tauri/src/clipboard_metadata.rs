@@ -1,8 +1,5 @@
-use enigo::{Direction, Enigo, Key, Keyboard, Settings};
 use serde::Serialize;
-use std::time::Duration;
-use tauri::{command, AppHandle, State};
-use tokio::time::sleep;
+use tauri::{command, State};
 use url_preview::PreviewService;
 
 #[cfg(target_os = "windows")]
@@ -118,31 +115,3 @@ pub async fn generate_url_preview(
         }),
     }
 }
-
-#[command]
-pub async fn paste(app: AppHandle) -> Result<(), String> {
-    let app_clone = app.clone();
-    app.run_on_main_thread(move || {
-        let _ = crate::visibility::hide_panel(&app_clone);
-    })
-    .map_err(|e| e.to_string())?;
-
-    sleep(Duration::from_millis(100)).await;
-
-    #[cfg(target_os = "macos")]
-    let modifier = Key::Meta;
-
-    #[cfg(not(target_os = "macos"))]
-    let modifier = Key::Control;
-
-    app.run_on_main_thread(move || {
-        let mut enigo = Enigo::new(&Settings::default()).unwrap();
-
-        enigo.key(modifier, Direction::Press).unwrap();
-        enigo.key(Key::Unicode('v'), Direction::Click).unwrap();
-        enigo.key(modifier, Direction::Release).unwrap();
-    })
-    .map_err(|e| e.to_string())?;
-
-    Ok(())
-}
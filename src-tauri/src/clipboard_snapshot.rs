@@ -0,0 +1,109 @@
+use clipboard_rs::{Clipboard, ClipboardContent, ClipboardContext, ContentFormat};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{command, AppHandle, Manager, State};
+use tokio::sync::watch;
+use tokio::time::sleep;
+
+/// How long the target application gets to read the pasted entry before the
+/// previous clipboard is put back.
+const RESTORE_DELAY: Duration = Duration::from_millis(400);
+
+/// Clipboard updates this soon after a restore are still Nabu's own writes.
+const CAPTURE_GRACE: Duration = Duration::from_millis(500);
+
+const SNAPSHOT_FORMATS: [ContentFormat; 5] = [
+    ContentFormat::Text,
+    ContentFormat::Html,
+    ContentFormat::Rtf,
+    ContentFormat::Image,
+    ContentFormat::Files,
+];
+
+pub struct ClipboardSnapshot {
+    contents: Mutex<Option<Vec<ClipboardContent>>>,
+    /// Watched by the connection to the daemon, which records for the app when
+    /// it is running.
+    suppress_capture: watch::Sender<bool>,
+}
+
+impl ClipboardSnapshot {
+    /// Saves the current clipboard and pauses capture until the paste finishes.
+    pub fn take(&self) -> Result<(), String> {
+        let contents = ClipboardContext::new()
+            .and_then(|clipboard| clipboard.get(&SNAPSHOT_FORMATS))
+            .map_err(|e| format!("Failed to snapshot clipboard: {}", e))?;
+
+        *self.contents.lock().unwrap() = Some(contents);
+        self.set_suppressing_capture(true);
+        Ok(())
+    }
+
+    pub fn is_suppressing_capture(&self) -> bool {
+        *self.suppress_capture.borrow()
+    }
+
+    pub fn set_suppressing_capture(&self, suppress: bool) {
+        self.suppress_capture
+            .send_if_modified(|current| std::mem::replace(current, suppress) != suppress);
+    }
+
+    pub fn watch_suppressing_capture(&self) -> watch::Receiver<bool> {
+        self.suppress_capture.subscribe()
+    }
+}
+
+pub fn init_clipboard_snapshot() -> ClipboardSnapshot {
+    ClipboardSnapshot {
+        contents: Mutex::new(None),
+        suppress_capture: watch::Sender::new(false),
+    }
+}
+
+/// Puts the snapshot back once the paste has landed, or discards it when
+/// `restore` is false.
+pub async fn finish_paste(app: &AppHandle, restore: bool) -> Result<(), String> {
+    let snapshot = app.state::<ClipboardSnapshot>();
+    let contents = snapshot.contents.lock().unwrap().take();
+    crate::tray::update_status(app);
+
+    let result = match contents {
+        Some(contents) if restore => {
+            sleep(RESTORE_DELAY).await;
+            ClipboardContext::new()
+                .and_then(|clipboard| {
+                    if contents.is_empty() {
+                        clipboard.clear()
+                    } else {
+                        clipboard.set(contents)
+                    }
+                })
+                .map_err(|e| format!("Failed to restore clipboard: {}", e))
+        }
+        _ => Ok(()),
+    };
+
+    if snapshot.is_suppressing_capture() {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            sleep(CAPTURE_GRACE).await;
+            app.state::<ClipboardSnapshot>()
+                .set_suppressing_capture(false);
+            crate::tray::update_status(&app);
+        });
+    }
+
+    result
+}
+
+#[command]
+pub fn snapshot_clipboard(state: State<ClipboardSnapshot>) -> Result<(), String> {
+    state.take()
+}
+
+/// Puts the snapshot back and resumes capture when the panel could not put the
+/// entry on the clipboard, so the paste never happened.
+#[command]
+pub async fn abort_paste(app: AppHandle) -> Result<(), String> {
+    finish_paste(&app, true).await
+}
@@ -0,0 +1,474 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::{command, AppHandle, Emitter, Manager};
+use tauri_plugin_opener::OpenerExt;
+use tokio::time::sleep;
+
+use crate::external_command::split_command_line;
+use crate::foreground::ForegroundWindow;
+use crate::history::History;
+use crate::paste::{self, PasteRule};
+use crate::settings::{ExternalCommand, SettingsStore};
+use crate::shortcuts::{self, ShortcutAction};
+
+pub const CONFIG_FILE: &str = "nabu.toml";
+
+/// How often the config file is checked for changes. Polling the modification
+/// time also catches editors that save by replacing the file.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Written on first start so the available options are easy to find.
+const CONFIG_TEMPLATE: &str = r#"# Nabu configuration. Changes are applied as soon as the file is saved.
+# Settings made here take precedence over the settings window.
+
+[capture]
+# Processes or window classes whose clipboard changes are never recorded.
+# ignore_apps = ["keepassxc", "1password"]
+
+[retention]
+# Favorites are always kept and don't count toward max_entries.
+# max_entries = 1000
+# max_age_days = 30
+
+[shortcuts]
+# A key with modifiers, like "ctrl+shift+space", or a chord like "ctrl+k v".
+# These override the shortcuts set in the settings window.
+# toggle = "ctrl+shift+space"
+# paste_plain = "ctrl+shift+v"
+# paste_next = "ctrl+k n"
+
+# Checked before the rules from the settings window and the built-in ones for
+# terminals.
+# Strategies: ctrl_v, ctrl_shift_v, shift_insert, type_text.
+# [[paste_rules]]
+# app = "code"
+# strategy = "ctrl_v"
+
+# Listed with the external commands in the entry menu, replacing one with the
+# same name.
+# [[transforms]]
+# name = "Sort lines"
+# command = "sort"
+"#;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CaptureRules {
+    pub ignore_apps: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Retention {
+    pub max_entries: Option<u32>,
+    pub max_age_days: Option<u32>,
+}
+
+/// Global shortcuts, in the syntax accepted by [`shortcuts::parse_accelerator`].
+/// Unset ones are left to the settings window.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShortcutBindings {
+    pub toggle: Option<String>,
+    pub paste_plain: Option<String>,
+    pub paste_next: Option<String>,
+}
+
+impl ShortcutBindings {
+    pub fn actions(&self) -> [(ShortcutAction, &Option<String>); 2] {
+        [
+            (ShortcutAction::PastePlain, &self.paste_plain),
+            (ShortcutAction::PasteNext, &self.paste_next),
+        ]
+    }
+}
+
+/// An external command transform, as written in the config file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigTransform {
+    pub name: String,
+    pub command: String,
+}
+
+/// The contents of `nabu.toml`. Every section is optional.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub capture: CaptureRules,
+    pub retention: Retention,
+    pub shortcuts: ShortcutBindings,
+    pub paste_rules: Vec<PasteRule>,
+    pub transforms: Vec<ConfigTransform>,
+}
+
+impl Config {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(toggle) = &self.shortcuts.toggle {
+            if shortcuts::parse_accelerator(toggle)?.len() != 1 {
+                return Err("shortcuts.toggle can't be a chord".into());
+            }
+        }
+        for (_, binding) in self.shortcuts.actions() {
+            if let Some(binding) = binding {
+                shortcuts::parse_accelerator(binding)?;
+            }
+        }
+
+        if self.retention.max_entries == Some(0) || self.retention.max_age_days == Some(0) {
+            return Err("retention limits must be greater than 0".into());
+        }
+
+        if self
+            .paste_rules
+            .iter()
+            .any(|rule| rule.app.trim().is_empty())
+        {
+            return Err("paste_rules entries need an app".into());
+        }
+
+        for (i, transform) in self.transforms.iter().enumerate() {
+            if transform.name.trim().is_empty() {
+                return Err(format!("Transform {} has no name", i + 1));
+            }
+            if self.transforms[..i]
+                .iter()
+                .any(|other| other.name == transform.name)
+            {
+                return Err(format!("Transform {} is listed twice", transform.name));
+            }
+            if split_command_line(&transform.command)?.is_empty() {
+                return Err(format!("Transform {} has no command", transform.name));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether clipboard changes made in `window` should be left out of history.
+    pub fn ignores(&self, window: &ForegroundWindow) -> bool {
+        self.capture
+            .ignore_apps
+            .iter()
+            .any(|app| window.matches(app))
+    }
+}
+
+/// The config file's location and problems, for the settings window.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigStatus {
+    pub path: String,
+    pub error: Option<String>,
+    pub transforms: Vec<ExternalCommand>,
+}
+
+/// The last valid config, and why the file was rejected if it has changed since.
+pub struct ConfigState {
+    config: Mutex<Config>,
+    error: Mutex<Option<String>>,
+}
+
+impl ConfigState {
+    pub fn get(&self) -> Config {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// Puts `loaded` into effect once `apply` has applied it over the current
+    /// config. On any error the current config stays, and the error is kept for
+    /// the settings window.
+    fn replace<F>(&self, loaded: Result<Config, String>, apply: F) -> Result<(), String>
+    where
+        F: FnOnce(&Config, &Config) -> Result<(), String>,
+    {
+        let result = loaded.and_then(|config| {
+            apply(&self.get(), &config)?;
+            *self.config.lock().unwrap() = config;
+            Ok(())
+        });
+        *self.error.lock().unwrap() = result.clone().err();
+        result
+    }
+}
+
+pub fn init_config_state() -> ConfigState {
+    ConfigState {
+        config: Mutex::new(Config::default()),
+        error: Mutex::new(None),
+    }
+}
+
+fn config_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    app_handle
+        .path()
+        .app_config_dir()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .map_err(|e| e.to_string())
+}
+
+fn read_config(app_handle: &AppHandle) -> Result<Config, String> {
+    let path = config_path(app_handle)?;
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Config::default()),
+        Err(e) => return Err(format!("Failed to read {:?}: {}", path, e)),
+    };
+
+    parse_config(&text)
+}
+
+fn parse_config(text: &str) -> Result<Config, String> {
+    let config: Config = toml::from_str(text).map_err(|e| e.to_string())?;
+    config.validate()?;
+    Ok(config)
+}
+
+fn config_status(app_handle: &AppHandle) -> ConfigStatus {
+    let state = app_handle.state::<ConfigState>();
+    ConfigStatus {
+        path: config_path(app_handle)
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        error: state.error.lock().unwrap().clone(),
+        transforms: state
+            .get()
+            .transforms
+            .into_iter()
+            .map(|transform| ExternalCommand {
+                name: transform.name,
+                command_line: transform.command,
+            })
+            .collect(),
+    }
+}
+
+/// Reads the config file and applies it. If it can't be read, is invalid or can't
+/// be applied, the previous config stays in effect and the next change retries.
+/// Problems are reported to the settings window.
+pub fn reload_config(app_handle: &AppHandle) {
+    let result = app_handle
+        .state::<ConfigState>()
+        .replace(read_config(app_handle), |previous, config| {
+            apply_config(app_handle, previous, config)
+        });
+
+    if let Err(e) = result {
+        log::warn!("Problem with {}: {}", CONFIG_FILE, e);
+    }
+    let _ = app_handle.emit("config-changed", config_status(app_handle));
+}
+
+/// Applies the parts of `config` that differ from `previous`. Shortcuts go first
+/// since they can fail, and applying the same config again is harmless.
+fn apply_config(app_handle: &AppHandle, previous: &Config, config: &Config) -> Result<(), String> {
+    // The daemon has no global shortcuts; the app binds them.
+    if app_handle.try_state::<shortcuts::AppState>().is_some() {
+        apply_shortcuts(app_handle, &previous.shortcuts, &config.shortcuts)?;
+    }
+
+    if previous.paste_rules != config.paste_rules {
+        // The daemon doesn't paste, and has no settings store.
+        let saved = app_handle
+            .try_state::<SettingsStore>()
+            .map(|settings| settings.get().paste_rules)
+            .unwrap_or_default();
+        paste::apply_paste_rules(app_handle, &config.paste_rules, &saved);
+    }
+
+    prune_history(app_handle);
+    Ok(())
+}
+
+/// Binds the shortcuts that changed. Ones that are no longer set go back to the
+/// ones saved in the settings window.
+fn apply_shortcuts(
+    app_handle: &AppHandle,
+    previous: &ShortcutBindings,
+    bindings: &ShortcutBindings,
+) -> Result<(), String> {
+    if previous.toggle != bindings.toggle {
+        let shortcut = match &bindings.toggle {
+            Some(toggle) => shortcuts::parse_accelerator(toggle)?[0],
+            None => shortcuts::saved_toggle(app_handle)
+                .unwrap_or_else(|_| shortcuts::default_shortcut()),
+        };
+        shortcuts::bind_toggle(app_handle, shortcut)?;
+    }
+
+    let saved = app_handle.state::<SettingsStore>().get().global_shortcuts;
+    shortcuts::sync_action_shortcuts(app_handle, &saved, bindings)
+}
+
+/// Applies the retention limits, and has the panel reload if anything was deleted.
+pub fn prune_history(app_handle: &AppHandle) {
+    let retention = app_handle.state::<ConfigState>().get().retention;
+    if retention == Retention::default() {
+        return;
+    }
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let pruned = app_handle
+            .state::<History>()
+            .prune(retention.max_entries, retention.max_age_days)
+            .await;
+        match pruned {
+            Ok(0) => {}
+            Ok(_) => {
+                crate::tray::refresh_menu(&app_handle);
+                let _ = app_handle.emit("history-cleared", ());
+            }
+            Err(e) => log::error!("Failed to apply retention limits: {}", e),
+        }
+    });
+}
+
+/// Writes the commented template if there is no config file yet, applies the
+/// file, and reloads it whenever it changes.
+pub fn watch_config(app_handle: &AppHandle) {
+    if let Ok(path) = config_path(app_handle) {
+        if !path.exists() {
+            if let Some(dir) = path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            let _ = std::fs::write(&path, CONFIG_TEMPLATE);
+        }
+    }
+
+    let modified = |app_handle: &AppHandle| -> Option<SystemTime> {
+        let path = config_path(app_handle).ok()?;
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    };
+
+    reload_config(app_handle);
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut last_modified = modified(&app_handle);
+        loop {
+            sleep(POLL_INTERVAL).await;
+            let current = modified(&app_handle);
+            if current != last_modified {
+                last_modified = current;
+                reload_config(&app_handle);
+            }
+        }
+    });
+}
+
+#[command]
+pub fn get_config_status(app: AppHandle) -> ConfigStatus {
+    config_status(&app)
+}
+
+#[command]
+pub fn open_config_file(app: AppHandle) -> Result<(), String> {
+    let path = config_path(&app)?;
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open {:?}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paste::PasteStrategy;
+
+    /// The template with every commented-out option enabled.
+    fn uncommented_template() -> String {
+        CONFIG_TEMPLATE
+            .lines()
+            .map(|line| match line.strip_prefix("# ") {
+                Some(option)
+                    if option.starts_with("[[")
+                        || option.split_once(" = ").is_some_and(|(key, _)| {
+                            key.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+                        }) =>
+                {
+                    option
+                }
+                _ => line,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn template_parses_to_the_defaults() {
+        assert_eq!(parse_config(CONFIG_TEMPLATE), Ok(Config::default()));
+    }
+
+    #[test]
+    fn template_options_are_all_valid() {
+        let config = parse_config(&uncommented_template()).unwrap();
+        assert_eq!(config.capture.ignore_apps, ["keepassxc", "1password"]);
+        assert_eq!(config.retention.max_entries, Some(1000));
+        assert_eq!(config.retention.max_age_days, Some(30));
+        assert_eq!(config.shortcuts.paste_next.as_deref(), Some("ctrl+k n"));
+        assert_eq!(
+            config.paste_rules,
+            [PasteRule {
+                app: "code".into(),
+                strategy: PasteStrategy::CtrlV,
+            }]
+        );
+        assert_eq!(config.transforms[0].command, "sort");
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        for text in [
+            "[retension]\nmax_entries = 5",
+            "[capture]\nignore = [\"x\"]",
+            "[shortcuts]\npaste = \"ctrl+v\"",
+            "[[transforms]]\nname = \"a\"\ncommand = \"sort\"\nargs = []",
+        ] {
+            assert!(parse_config(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn validates_shortcuts() {
+        assert!(parse_config("[shortcuts]\ntoggle = \"ctrl+k v\"").is_err());
+        assert!(parse_config("[shortcuts]\npaste_plain = \"ctrl+nope\"").is_err());
+        assert!(parse_config("[shortcuts]\npaste_plain = \"ctrl+k v\"").is_ok());
+    }
+
+    #[test]
+    fn validates_retention_rules_and_transforms() {
+        for text in [
+            "[retention]\nmax_entries = 0",
+            "[retention]\nmax_age_days = 0",
+            "[[paste_rules]]\napp = \" \"\nstrategy = \"ctrl_v\"",
+            "[[transforms]]\nname = \"\"\ncommand = \"sort\"",
+            "[[transforms]]\nname = \"a\"\ncommand = \"\"",
+            "[[transforms]]\nname = \"a\"\ncommand = \"sort\"\n[[transforms]]\nname = \"a\"\ncommand = \"tac\"",
+        ] {
+            assert!(parse_config(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn keeps_the_previous_config_when_a_reload_fails() {
+        let state = init_config_state();
+        let valid = parse_config("[retention]\nmax_entries = 5").unwrap();
+        assert_eq!(state.replace(Ok(valid.clone()), |_, _| Ok(())), Ok(()));
+
+        let invalid = parse_config("[retention]\nmax_entries = 0");
+        assert!(state.replace(invalid, |_, _| Ok(())).is_err());
+        assert_eq!(state.get(), valid);
+        assert!(state.error.lock().unwrap().is_some());
+
+        let unapplied = Config::default();
+        assert_eq!(
+            state.replace(Ok(unapplied), |_, _| Err("busy".into())),
+            Err("busy".into())
+        );
+        assert_eq!(state.get(), valid);
+
+        assert_eq!(state.replace(Ok(Config::default()), |_, _| Ok(())), Ok(()));
+        assert_eq!(state.get(), Config::default());
+        assert!(state.error.lock().unwrap().is_none());
+    }
+}
@@ -0,0 +1,332 @@
+use serde::{Deserialize, Serialize};
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Listener, Manager, Wry};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::timeout;
+
+use crate::capture::{set_paused, CaptureState};
+use crate::clipboard_snapshot::ClipboardSnapshot;
+use crate::recorder::{notify_entry_recorded, start_recording, stop_recording};
+
+/// `nabu --daemon` records history without any windows, and the app attaches to
+/// it instead of recording itself.
+pub const DAEMON_FLAG: &str = "--daemon";
+
+/// Where the daemon says how to reach it, in the app config directory.
+const ADDRESS_FILE: &str = "daemon.json";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How long to wait before accepting connections again after an error.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// How often an app that is recording itself looks for a daemon that started
+/// after it.
+const DAEMON_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DaemonAddress {
+    port: u16,
+    /// Sent first by the app, so other local users can't attach.
+    token: String,
+}
+
+/// One line of the connection between the app and the daemon.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Message {
+    Hello {
+        token: String,
+    },
+    /// The daemon's answer to a valid `Hello`.
+    Welcome,
+    /// From the app: capture was paused or resumed.
+    Paused {
+        paused: bool,
+    },
+    /// From the app: Nabu is pasting and will restore the clipboard, so changes
+    /// until it is done aren't the user's.
+    HoldingClipboard {
+        holding: bool,
+    },
+    /// From the daemon: it added or bumped an entry.
+    EntryRecorded,
+}
+
+pub fn is_daemon_mode() -> bool {
+    std::env::args().any(|arg| arg == DAEMON_FLAG)
+}
+
+fn address_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    app_handle
+        .path()
+        .app_config_dir()
+        .map(|dir| dir.join(ADDRESS_FILE))
+        .map_err(|e| e.to_string())
+}
+
+fn write_address(app_handle: &AppHandle, address: &DaemonAddress) -> Result<(), String> {
+    let path = address_path(app_handle)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    }
+    let json = serde_json::to_string(address).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to restrict {:?}: {}", path, e))?;
+    }
+    Ok(())
+}
+
+async fn send(writer: &mut OwnedWriteHalf, message: &Message) -> std::io::Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await
+}
+
+/// Connects to the daemon named in the address file, if one is running and
+/// accepts the token.
+async fn connect(
+    app_handle: &AppHandle,
+) -> Option<(Lines<BufReader<OwnedReadHalf>>, OwnedWriteHalf)> {
+    let address: DaemonAddress = std::fs::read_to_string(address_path(app_handle).ok()?)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())?;
+
+    let stream = timeout(
+        CONNECT_TIMEOUT,
+        TcpStream::connect((Ipv4Addr::LOCALHOST, address.port)),
+    )
+    .await
+    .ok()?
+    .ok()?;
+    let (reader, mut writer) = stream.into_split();
+    send(
+        &mut writer,
+        &Message::Hello {
+            token: address.token,
+        },
+    )
+    .await
+    .ok()?;
+
+    let mut lines = BufReader::new(reader).lines();
+    let welcome = timeout(CONNECT_TIMEOUT, lines.next_line())
+        .await
+        .ok()?
+        .ok()??;
+    match serde_json::from_str(&welcome) {
+        Ok(Message::Welcome) => Some((lines, writer)),
+        _ => None,
+    }
+}
+
+/// Leaves recording to a running daemon, or records in the app while there is
+/// none. A daemon started later takes over, and if it stops the app records
+/// again.
+pub fn attach_or_record(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Some((lines, writer)) = connect(&app_handle).await {
+                stop_recording(&app_handle);
+                relay(&app_handle, lines, writer).await;
+            }
+            start_recording(&app_handle);
+            tokio::time::sleep(DAEMON_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Tells the daemon about pauses and pastes in the app, and the app about
+/// entries the daemon records, until the connection closes.
+async fn relay(
+    app_handle: &AppHandle,
+    mut lines: Lines<BufReader<OwnedReadHalf>>,
+    mut writer: OwnedWriteHalf,
+) {
+    let paused = app_handle.state::<CaptureState>().is_paused();
+    if send(&mut writer, &Message::Paused { paused })
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    let (paused_sender, mut paused_changes) = mpsc::unbounded_channel();
+    let listener = app_handle.listen("capture-paused-changed", move |event| {
+        if let Ok(paused) = serde_json::from_str::<bool>(event.payload()) {
+            let _ = paused_sender.send(paused);
+        }
+    });
+    let mut holding = app_handle
+        .state::<ClipboardSnapshot>()
+        .watch_suppressing_capture();
+
+    loop {
+        let message = tokio::select! {
+            line = lines.next_line() => match line {
+                Ok(Some(line)) => {
+                    if let Ok(Message::EntryRecorded) = serde_json::from_str(&line) {
+                        notify_entry_recorded(app_handle);
+                    }
+                    continue;
+                }
+                _ => break,
+            },
+            Ok(()) = holding.changed() => Message::HoldingClipboard {
+                holding: *holding.borrow_and_update(),
+            },
+            Some(paused) = paused_changes.recv() => Message::Paused { paused },
+        };
+        if send(&mut writer, &message).await.is_err() {
+            break;
+        }
+    }
+
+    app_handle.unlisten(listener);
+}
+
+/// Accepts connections from the app and records history until the process is
+/// stopped. Fails if another daemon is already running.
+pub async fn serve(app_handle: AppHandle) -> Result<(), String> {
+    if connect(&app_handle).await.is_some() {
+        return Err("Another Nabu daemon is already running".into());
+    }
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .await
+        .map_err(|e| format!("Failed to listen for the app: {}", e))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    let token = uuid::Uuid::new_v4().to_string();
+    write_address(
+        &app_handle,
+        &DaemonAddress {
+            port,
+            token: token.clone(),
+        },
+    )?;
+
+    let (recorded, _) = broadcast::channel(16);
+    let sender = recorded.clone();
+    app_handle.listen("entry-recorded", move |_| {
+        let _ = sender.send(());
+    });
+
+    start_recording(&app_handle);
+
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                // Errors like running out of file descriptors last a while.
+                log::warn!("Failed to accept a connection from the app: {}", e);
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                continue;
+            }
+        };
+        let app_handle = app_handle.clone();
+        let token = token.clone();
+        let recorded = recorded.subscribe();
+        tauri::async_runtime::spawn(async move {
+            serve_app(&app_handle, stream, &token, recorded).await;
+            // The app may have quit in the middle of a paste.
+            app_handle
+                .state::<ClipboardSnapshot>()
+                .set_suppressing_capture(false);
+        });
+    }
+}
+
+async fn serve_app(
+    app_handle: &AppHandle,
+    stream: TcpStream,
+    token: &str,
+    mut recorded: broadcast::Receiver<()>,
+) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    let hello = lines.next_line().await.ok().flatten();
+    match hello.and_then(|line| serde_json::from_str(&line).ok()) {
+        Some(Message::Hello { token: sent }) if sent == token => {}
+        _ => return,
+    }
+    if send(&mut writer, &Message::Welcome).await.is_err() {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            line = lines.next_line() => match line {
+                Ok(Some(line)) => match serde_json::from_str(&line) {
+                    Ok(Message::Paused { paused }) => set_paused(app_handle, paused),
+                    Ok(Message::HoldingClipboard { holding }) => app_handle
+                        .state::<ClipboardSnapshot>()
+                        .set_suppressing_capture(holding),
+                    _ => {}
+                },
+                _ => break,
+            },
+            received = recorded.recv() => {
+                if matches!(received, Err(broadcast::error::RecvError::Closed))
+                    || send(&mut writer, &Message::EntryRecorded).await.is_err()
+                {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Runs the daemon: history recording, the config file and retention limits,
+/// with no windows, tray or global shortcuts.
+pub fn run(context: tauri::Context<Wry>) {
+    tauri::Builder::default()
+        .plugin(tauri_plugin_log::Builder::new().build())
+        .setup(|app| {
+            let app_handle = app.handle();
+
+            #[cfg(target_os = "macos")]
+            app.set_activation_policy(tauri::ActivationPolicy::Accessory);
+
+            app.manage(crate::clipboard_snapshot::init_clipboard_snapshot());
+            app.manage(crate::paste::init_paste_rules());
+            app.manage(crate::history::init_history(app_handle)?);
+            app.manage(crate::capture::init_capture_state());
+            // Capture status goes through the tray, which the daemon doesn't show.
+            app.manage(crate::tray::init_tray_state());
+            app.manage(crate::config::init_config_state());
+            app.manage(crate::recorder::init_recorder());
+            crate::config::watch_config(app_handle);
+
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = serve(app_handle.clone()).await {
+                    log::error!("{}", e);
+                    app_handle.exit(1);
+                }
+            });
+            Ok(())
+        })
+        .build(context)
+        .expect("error while building tauri application")
+        .run(|_, event| {
+            // With no windows, nothing else keeps the daemon running.
+            if let tauri::RunEvent::ExitRequested {
+                api, code: None, ..
+            } = event
+            {
+                api.prevent_exit();
+            }
+        });
+}
@@ -0,0 +1,240 @@
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{command, AppHandle, Emitter, Manager};
+use tauri_plugin_opener::OpenerExt;
+use tokio::time::sleep;
+
+use crate::entry::ClipboardEntry;
+use crate::external_command::split_command_line;
+use crate::settings::SettingsStore;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// When the editor can't be waited on, stop watching and delete the temp file
+/// after this long without a save.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// An editor command that exits this soon only handed the file to an editor
+/// that was already running, like `code` without `--wait`, so it can't be
+/// waited on.
+const HANDOFF_WINDOW: Duration = Duration::from_secs(5);
+
+/// Emitted as `entry-edited` each time the user saves the file in their editor.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EditedEntry {
+    entry_id: i64,
+    content: String,
+    content_type: String,
+    preview: String,
+}
+
+fn file_extension(content_type: &str) -> &'static str {
+    match content_type {
+        "html" => "html",
+        "rtf" => "rtf",
+        _ => "txt",
+    }
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+/// The editor command: the one from the settings window, else `$VISUAL` or
+/// `$EDITOR`. `None` means the system's default application for the file type.
+///
+/// The variables usually name terminal editors like vim, which can't run
+/// without one, so they are only used when Nabu was started from a terminal.
+fn editor_command(app: &AppHandle) -> Option<String> {
+    let configured = app.state::<SettingsStore>().get().external_editor;
+    let from_env = |name: &str| {
+        std::env::var(name)
+            .ok()
+            .filter(|_| std::io::stdin().is_terminal())
+    };
+    [Some(configured), from_env("VISUAL"), from_env("EDITOR")]
+        .into_iter()
+        .flatten()
+        .map(|editor| editor.trim().to_string())
+        .find(|editor| !editor.is_empty())
+}
+
+/// Creates `path` for the owner only, failing if something is already there, so
+/// other users can't read the entry or swap the file for a link.
+fn create_private_file(path: &Path) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+/// Writes `entry` to a temp file and opens it in the editor from the settings,
+/// `$VISUAL` or `$EDITOR`, or the system's default application for the file type.
+/// Every save is reported through `entry-edited`.
+#[command]
+pub async fn edit_entry_externally(app: AppHandle, entry: ClipboardEntry) -> Result<(), String> {
+    if entry.content_type == "image" {
+        return Err(format!(
+            "Entry {} is an image and can't be edited as text",
+            entry.id
+        ));
+    }
+
+    let stamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    let path = std::env::temp_dir().join(format!(
+        "nabu-entry-{}-{}.{}",
+        entry.id,
+        stamp,
+        file_extension(&entry.content_type)
+    ));
+    create_private_file(&path)
+        .and_then(|mut file| file.write_all(entry.content.as_bytes()))
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+
+    let editor = match open_in_editor(&app, &path, editor_command(&app).as_deref()) {
+        Ok(editor) => editor,
+        Err(e) => {
+            let _ = std::fs::remove_file(&path);
+            return Err(e);
+        }
+    };
+
+    tauri::async_runtime::spawn(watch_edits(app, path, entry, editor));
+    Ok(())
+}
+
+/// Opens `path`, returning the editor process when it is one we started.
+fn open_in_editor(
+    app: &AppHandle,
+    path: &Path,
+    editor: Option<&str>,
+) -> Result<Option<Child>, String> {
+    match editor {
+        Some(editor) => {
+            let args = split_command_line(editor)?;
+            let (program, args) = args
+                .split_first()
+                .ok_or_else(|| "The editor command is empty".to_string())?;
+            std::process::Command::new(program)
+                .args(args)
+                .arg(path)
+                .spawn()
+                .map(Some)
+                .map_err(|e| format!("Failed to start {}: {}", program, e))
+        }
+        None => app
+            .opener()
+            .open_path(path.to_string_lossy(), None::<&str>)
+            .map(|_| None)
+            .map_err(|e| format!("Failed to open {:?}: {}", path, e)),
+    }
+}
+
+/// Reports saves until the editor exits, or for editors that can't be waited on,
+/// until [`IDLE_TIMEOUT`] passes without one. Then deletes the temp file.
+async fn watch_edits(
+    app: AppHandle,
+    path: PathBuf,
+    entry: ClipboardEntry,
+    mut editor: Option<Child>,
+) {
+    let mut last_modified = modified_at(&path);
+    let mut last_content = entry.content.clone();
+    let started = Instant::now();
+    let mut last_activity = started;
+
+    loop {
+        sleep(POLL_INTERVAL).await;
+        // `try_wait` reaps the editor once it has exited. Checked before reading,
+        // so a save made just before quitting is still reported.
+        let exited = editor
+            .as_mut()
+            .is_some_and(|child| !matches!(child.try_wait(), Ok(None)));
+        if exited {
+            // An early exit means the file went to an editor that was already
+            // running; fall back to the idle timeout.
+            editor = None;
+            if started.elapsed() >= HANDOFF_WINDOW {
+                report_edit(&app, &path, &entry, &mut last_modified, &mut last_content);
+                break;
+            }
+        }
+        if editor.is_none() && last_activity.elapsed() >= IDLE_TIMEOUT {
+            report_edit(&app, &path, &entry, &mut last_modified, &mut last_content);
+            break;
+        }
+
+        if modified_at(&path).is_none() {
+            // Some editors save by replacing the file; give them a moment.
+            sleep(POLL_INTERVAL).await;
+            if modified_at(&path).is_none() {
+                break;
+            }
+            continue;
+        }
+        if report_edit(&app, &path, &entry, &mut last_modified, &mut last_content) {
+            last_activity = Instant::now();
+        }
+    }
+
+    let _ = std::fs::remove_file(&path);
+    // The file was deleted while the editor is still open; reap it when it exits.
+    if let Some(mut child) = editor {
+        tauri::async_runtime::spawn_blocking(move || child.wait());
+    }
+}
+
+/// Emits `entry-edited` if the file was saved with new content since the last
+/// call. Returns whether it was saved at all.
+fn report_edit(
+    app: &AppHandle,
+    path: &Path,
+    entry: &ClipboardEntry,
+    last_modified: &mut Option<SystemTime>,
+    last_content: &mut String,
+) -> bool {
+    let modified = modified_at(path);
+    if modified.is_none() || modified == *last_modified {
+        return false;
+    }
+    *last_modified = modified;
+
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return true;
+    };
+    if content.trim().is_empty() || content == *last_content {
+        return true;
+    }
+
+    let revised = ClipboardEntry {
+        content: content.clone(),
+        preview: None,
+        ..entry.clone()
+    };
+    let preview = revised.plain_text().unwrap_or_else(|_| content.clone());
+    let _ = app.emit(
+        "entry-edited",
+        EditedEntry {
+            entry_id: entry.id,
+            content: content.clone(),
+            content_type: entry.content_type.clone(),
+            preview,
+        },
+    );
+    *last_content = content;
+    true
+}
@@ -0,0 +1,111 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clipboard_rs::common::{RustImage, RustImageData};
+use clipboard_rs::{Clipboard, ClipboardContent, ClipboardContext};
+use serde::{Deserialize, Serialize};
+
+/// A history entry as stored by the panel's `clipboard_entries` table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardEntry {
+    pub id: i64,
+    pub content: String,
+    pub content_type: String,
+    pub preview: Option<String>,
+}
+
+impl ClipboardEntry {
+    /// The entry as plain text, or an error for images and files.
+    pub fn plain_text(&self) -> Result<String, String> {
+        let text_twin = self
+            .preview
+            .as_deref()
+            .filter(|preview| !preview.is_empty());
+
+        match self.content_type.as_str() {
+            "html" => Ok(text_twin
+                .map(str::to_string)
+                .unwrap_or_else(|| crate::plain_text::html_to_text(&self.content))),
+            "rtf" => Ok(text_twin
+                .map(str::to_string)
+                .unwrap_or_else(|| crate::plain_text::rtf_to_text(&self.content))),
+            "image" | "file" => Err(format!(
+                "Entry {} is {} content and has no plain-text rendition",
+                self.id, self.content_type
+            )),
+            _ => Ok(self.content.clone()),
+        }
+    }
+
+    /// The first line of the entry's preview, shortened to `max_chars`.
+    pub fn summary(&self, max_chars: usize) -> String {
+        let preview = self.preview.as_deref().unwrap_or(&self.content);
+        let line = preview
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or_default()
+            .trim();
+
+        if line.chars().count() > max_chars {
+            let truncated: String = line.chars().take(max_chars.saturating_sub(1)).collect();
+            format!("{}…", truncated)
+        } else {
+            line.to_string()
+        }
+    }
+
+    /// Places the entry on the clipboard in the same formats the panel writes.
+    pub fn write_to_clipboard(&self) -> Result<(), String> {
+        let contents = match self.content_type.as_str() {
+            "image" => {
+                let bytes = STANDARD
+                    .decode(&self.content)
+                    .map_err(|e| format!("Invalid image data: {}", e))?;
+                let image = RustImageData::from_bytes(&bytes)
+                    .map_err(|e| format!("Invalid image data: {}", e))?;
+                vec![ClipboardContent::Image(image)]
+            }
+            "html" => vec![
+                ClipboardContent::Html(self.content.clone()),
+                ClipboardContent::Text(self.plain_text()?),
+            ],
+            "rtf" => vec![
+                ClipboardContent::Rtf(self.content.clone()),
+                ClipboardContent::Text(self.plain_text()?),
+            ],
+            "file" => vec![ClipboardContent::Files(
+                self.content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            )],
+            _ => vec![ClipboardContent::Text(self.content.clone())],
+        };
+
+        ClipboardContext::new()
+            .and_then(|clipboard| clipboard.set(contents))
+            .map_err(|e| format!("Failed to write entry to clipboard: {}", e))
+    }
+}
+
+/// Joins the plain-text renditions of `entries` in order. Images and files are rejected.
+pub fn join_plain_text(entries: &[ClipboardEntry], separator: &str) -> Result<String, String> {
+    let unsupported: Vec<String> = entries
+        .iter()
+        .filter(|entry| matches!(entry.content_type.as_str(), "image" | "file"))
+        .map(|entry| entry.id.to_string())
+        .collect();
+    if !unsupported.is_empty() {
+        return Err(format!(
+            "Images and files can't be combined as text (entries {})",
+            unsupported.join(", ")
+        ));
+    }
+
+    let texts = entries
+        .iter()
+        .map(ClipboardEntry::plain_text)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(texts.join(separator))
+}
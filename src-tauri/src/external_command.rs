@@ -0,0 +1,201 @@
+use std::process::Stdio;
+use std::time::Duration;
+use tauri::{command, AppHandle, Manager};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::config::ConfigState;
+use crate::entry::ClipboardEntry;
+use crate::paste::{write_and_paste, write_plain_text, PasteError};
+use crate::settings::SettingsStore;
+
+/// Commands still running after this long are killed.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Splits a command line into a program and its arguments. Quoting works as in
+/// POSIX shells, but nothing else does: no pipes, globs, variables or redirects.
+/// Backslashes only escape quotes, whitespace and backslashes, so Windows paths
+/// can be written as-is.
+pub fn split_command_line(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_arg = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated ' in command".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_arg = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.peek(), Some('"' | '\\')) => {
+                            current.extend(chars.next())
+                        }
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated \" in command".to_string()),
+                    }
+                }
+            }
+            '\\' if chars
+                .peek()
+                .is_some_and(|next| next.is_whitespace() || matches!(next, '\'' | '"' | '\\')) =>
+            {
+                in_arg = true;
+                current.extend(chars.next());
+            }
+            c if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                in_arg = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// Runs `command_line` with `input` on stdin and returns its stdout.
+pub async fn run_command(command_line: &str, input: &str) -> Result<String, String> {
+    let args = split_command_line(command_line)?;
+    let (program, args) = args
+        .split_first()
+        .ok_or_else(|| "The command is empty".to_string())?;
+
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    // Don't flash a console window for every run.
+    #[cfg(target_os = "windows")]
+    command.creation_flags(0x0800_0000);
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", program, e))?;
+
+    // Write from a separate task so a command that produces output before reading
+    // all of its input can't deadlock against us.
+    if let Some(mut stdin) = child.stdin.take() {
+        let input = input.to_string();
+        tauri::async_runtime::spawn(async move {
+            let _ = stdin.write_all(input.as_bytes()).await;
+        });
+    }
+
+    // Dropping the child on timeout kills it.
+    let output = timeout(COMMAND_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| {
+            format!(
+                "{} did not finish within {} s",
+                program,
+                COMMAND_TIMEOUT.as_secs()
+            )
+        })?
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let status = match output.status.code() {
+            Some(code) => format!("exited with code {}", code),
+            None => "was terminated by a signal".to_string(),
+        };
+        return Err(match stderr.trim() {
+            "" => format!("{} {}", program, status),
+            stderr => format!("{} {}: {}", program, status, stderr),
+        });
+    }
+
+    let mut stdout = String::from_utf8(output.stdout)
+        .map_err(|_| format!("The output of {} is not text", program))?;
+
+    // Most tools end their output with a newline even when the input had none.
+    if !input.ends_with('\n') && stdout.ends_with('\n') {
+        stdout.pop();
+        if stdout.ends_with('\r') {
+            stdout.pop();
+        }
+    }
+    Ok(stdout)
+}
+
+/// What gets written to a command's stdin: the raw content, so e.g. HTML can be
+/// converted with pandoc, or the path list for files.
+fn command_input(entry: &ClipboardEntry) -> Result<&str, String> {
+    match entry.content_type.as_str() {
+        "image" => Err(format!(
+            "Entry {} is an image and can't be piped to a command",
+            entry.id
+        )),
+        _ => Ok(&entry.content),
+    }
+}
+
+/// The command line of the transform called `name`: from `[[transforms]]` in the
+/// config file, or else from the external commands saved in the settings window.
+/// The panel only ever names one, so it can't run arbitrary programs.
+fn command_line_for(app: &AppHandle, name: &str) -> Result<String, String> {
+    let config = app.state::<ConfigState>().get();
+    if let Some(transform) = config.transforms.into_iter().find(|t| t.name == name) {
+        return Ok(transform.command);
+    }
+
+    app.state::<SettingsStore>()
+        .get()
+        .external_commands
+        .into_iter()
+        .find(|command| command.name == name)
+        .map(|command| command.command_line)
+        .ok_or_else(|| format!("No external command named {:?}", name))
+}
+
+/// Returns the output of the command called `name` for `entry`, e.g. to save as a
+/// new entry.
+#[command]
+pub async fn run_external_command(
+    app: AppHandle,
+    entry: ClipboardEntry,
+    name: String,
+) -> Result<String, String> {
+    let command_line = command_line_for(&app, &name)?;
+    run_command(&command_line, command_input(&entry)?).await
+}
+
+/// Pastes the output of the command called `name` for `entry` as plain text.
+#[command]
+pub async fn paste_external_command(
+    app: AppHandle,
+    entry: ClipboardEntry,
+    name: String,
+    restore_clipboard: Option<bool>,
+) -> Result<(), PasteError> {
+    let restore_clipboard = restore_clipboard.unwrap_or(false);
+    let command_line = command_line_for(&app, &name)?;
+    let text = run_command(&command_line, command_input(&entry)?).await?;
+
+    write_and_paste(&app, restore_clipboard, || write_plain_text(text)).await
+}
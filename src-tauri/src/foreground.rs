@@ -0,0 +1,354 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::time::sleep;
+
+const FOCUS_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// The window that currently has keyboard focus.
+#[derive(Debug, Clone)]
+pub struct ForegroundWindow {
+    pub id: u64,
+    pub pid: Option<u32>,
+    pub process: Option<String>,
+    pub class: Option<String>,
+}
+
+impl ForegroundWindow {
+    /// Whether the window belongs to Nabu itself.
+    pub fn is_own(&self) -> bool {
+        self.pid == Some(std::process::id())
+    }
+
+    /// A human-readable name for error messages.
+    pub fn describe(&self) -> String {
+        self.process
+            .clone()
+            .or_else(|| self.class.clone())
+            .unwrap_or_else(|| format!("window {}", self.id))
+    }
+
+    /// Whether `app` names this window's process or window class.
+    pub fn matches(&self, app: &str) -> bool {
+        let normalize = |name: &str| name.trim().trim_end_matches(".exe").to_lowercase();
+        let app = normalize(app);
+
+        [&self.process, &self.class]
+            .into_iter()
+            .flatten()
+            .any(|name| normalize(name) == app)
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn foreground_window() -> Option<ForegroundWindow> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetClassNameW, GetForegroundWindow, GetWindowThreadProcessId,
+    };
+
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd == 0 {
+            return None;
+        }
+
+        let mut class_buffer = [0u16; 256];
+        let class_len = GetClassNameW(hwnd, class_buffer.as_mut_ptr(), class_buffer.len() as i32);
+        let class =
+            (class_len > 0).then(|| String::from_utf16_lossy(&class_buffer[..class_len as usize]));
+
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+
+        let mut process = None;
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle != 0 {
+            let mut path_buffer = [0u16; 1024];
+            let mut path_len = path_buffer.len() as u32;
+            if QueryFullProcessImageNameW(
+                handle,
+                PROCESS_NAME_WIN32,
+                path_buffer.as_mut_ptr(),
+                &mut path_len,
+            ) != 0
+            {
+                let path = String::from_utf16_lossy(&path_buffer[..path_len as usize]);
+                process = path.rsplit('\\').next().map(str::to_string);
+            }
+            CloseHandle(handle);
+        }
+
+        Some(ForegroundWindow {
+            id: hwnd as u64,
+            pid: (pid != 0).then_some(pid),
+            process,
+            class,
+        })
+    }
+}
+
+/// A connection to the X server, kept open while focus is watched. Only X11
+/// exposes the active window; Wayland sessions can't connect, or see no windows.
+#[cfg(target_os = "linux")]
+struct X11Session {
+    conn: x11rb::rust_connection::RustConnection,
+    root: u32,
+    active_window_atom: u32,
+    pid_atom: u32,
+}
+
+#[cfg(target_os = "linux")]
+impl X11Session {
+    fn connect() -> Option<Self> {
+        use x11rb::connection::Connection;
+        use x11rb::protocol::xproto::ConnectionExt;
+
+        let (conn, screen_num) = x11rb::connect(None).ok()?;
+        let root = conn.setup().roots[screen_num].root;
+
+        let intern = |name: &[u8]| {
+            conn.intern_atom(false, name)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+                .map(|reply| reply.atom)
+        };
+        let active_window_atom = intern(b"_NET_ACTIVE_WINDOW")?;
+        let pid_atom = intern(b"_NET_WM_PID")?;
+
+        Some(X11Session {
+            conn,
+            root,
+            active_window_atom,
+            pid_atom,
+        })
+    }
+
+    fn active_window(&self) -> Option<ForegroundWindow> {
+        use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+
+        let conn = &self.conn;
+        let window = conn
+            .get_property(
+                false,
+                self.root,
+                self.active_window_atom,
+                AtomEnum::WINDOW,
+                0,
+                1,
+            )
+            .ok()?
+            .reply()
+            .ok()?
+            .value32()?
+            .next()?;
+        if window == 0 {
+            return None;
+        }
+
+        // WM_CLASS holds "instance\0class\0"; the class half is the stable identifier.
+        let class = conn
+            .get_property(false, window, AtomEnum::WM_CLASS, AtomEnum::STRING, 0, 256)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|reply| {
+                reply
+                    .value
+                    .split(|byte| *byte == 0)
+                    .rfind(|part| !part.is_empty())
+                    .map(|part| String::from_utf8_lossy(part).into_owned())
+            });
+
+        let pid = conn
+            .get_property(false, window, self.pid_atom, AtomEnum::CARDINAL, 0, 1)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|reply| reply.value32().and_then(|mut values| values.next()));
+        let process = pid
+            .and_then(|pid| std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok())
+            .map(|comm| comm.trim().to_string());
+
+        Some(ForegroundWindow {
+            id: window as u64,
+            pid,
+            process,
+            class,
+        })
+    }
+
+    /// Calls `on_change` with the active window each time the window manager
+    /// changes it, until the connection fails.
+    fn watch_active_window(
+        &self,
+        mut on_change: impl FnMut(Option<ForegroundWindow>),
+    ) -> Result<(), String> {
+        use x11rb::connection::Connection;
+        use x11rb::protocol::xproto::{ChangeWindowAttributesAux, ConnectionExt, EventMask};
+        use x11rb::protocol::Event;
+
+        let attributes = ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE);
+        self.conn
+            .change_window_attributes(self.root, &attributes)
+            .map_err(|e| e.to_string())?
+            .check()
+            .map_err(|e| e.to_string())?;
+
+        loop {
+            match self.conn.wait_for_event().map_err(|e| e.to_string())? {
+                Event::PropertyNotify(event) if event.atom == self.active_window_atom => {
+                    on_change(self.active_window())
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn foreground_window() -> Option<ForegroundWindow> {
+    X11Session::connect()?.active_window()
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn foreground_window() -> Option<ForegroundWindow> {
+    None
+}
+
+/// Asks the OS to give keyboard focus back to `window`.
+#[cfg(target_os = "windows")]
+pub fn activate_window(window: &ForegroundWindow) -> Result<(), String> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        IsIconic, SetForegroundWindow, ShowWindow, SW_RESTORE,
+    };
+
+    let hwnd = window.id as isize;
+    unsafe {
+        if IsIconic(hwnd) != 0 {
+            ShowWindow(hwnd, SW_RESTORE);
+        }
+        if SetForegroundWindow(hwnd) == 0 {
+            return Err(format!("Failed to activate {}", window.describe()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Asks the window manager to give keyboard focus back to `window`.
+#[cfg(target_os = "linux")]
+pub fn activate_window(window: &ForegroundWindow) -> Result<(), String> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{ClientMessageEvent, ConnectionExt, EventMask};
+
+    let (conn, screen_num) = x11rb::connect(None).map_err(|e| e.to_string())?;
+    let root = conn.setup().roots[screen_num].root;
+    let active_window_atom = conn
+        .intern_atom(false, b"_NET_ACTIVE_WINDOW")
+        .map_err(|e| e.to_string())?
+        .reply()
+        .map_err(|e| e.to_string())?
+        .atom;
+
+    // Source indication 2 marks the request as coming from a pager-like tool, which
+    // window managers honour without focus-stealing prevention.
+    let event = ClientMessageEvent::new(32, window.id as u32, active_window_atom, [2, 0, 0, 0, 0]);
+    conn.send_event(
+        false,
+        root,
+        EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+        event,
+    )
+    .map_err(|e| e.to_string())?;
+    conn.flush().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn activate_window(_window: &ForegroundWindow) -> Result<(), String> {
+    Ok(())
+}
+
+pub struct FocusState {
+    previous_window: Mutex<Option<ForegroundWindow>>,
+}
+
+pub fn init_focus_state() -> FocusState {
+    FocusState {
+        previous_window: Mutex::new(None),
+    }
+}
+
+/// Records the window the user was working in before the panel takes focus.
+pub fn remember_previous_window(app_handle: &AppHandle) {
+    remember_window(app_handle, foreground_window());
+}
+
+fn remember_window(app_handle: &AppHandle, window: Option<ForegroundWindow>) {
+    if let Some(window) = window.filter(|window| !window.is_own()) {
+        *app_handle
+            .state::<FocusState>()
+            .previous_window
+            .lock()
+            .unwrap() = Some(window);
+    }
+}
+
+/// Keeps remembering the focused window in the background. Linux trays don't
+/// report the pointer entering the icon, so the tray can't note the window to
+/// paste into before its menu takes focus; this has it noted already.
+pub fn track_previous_window(app_handle: &AppHandle) {
+    #[cfg(target_os = "linux")]
+    {
+        let app_handle = app_handle.clone();
+        std::thread::spawn(move || {
+            let Some(session) = X11Session::connect() else {
+                return;
+            };
+            remember_window(&app_handle, session.active_window());
+            if let Err(e) =
+                session.watch_active_window(|window| remember_window(&app_handle, window))
+            {
+                log::warn!("Stopped tracking the focused window: {}", e);
+            }
+        });
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = app_handle;
+}
+
+pub fn previous_window(app_handle: &AppHandle) -> Option<ForegroundWindow> {
+    app_handle
+        .state::<FocusState>()
+        .previous_window
+        .lock()
+        .unwrap()
+        .clone()
+}
+
+/// Polls until `window` has keyboard focus again. Returns false on timeout.
+pub async fn wait_for_focus(window: &ForegroundWindow, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+
+    // One connection for the whole wait rather than one per poll.
+    #[cfg(target_os = "linux")]
+    let session = X11Session::connect();
+    #[cfg(target_os = "linux")]
+    let focused = || session.as_ref().and_then(X11Session::active_window);
+    #[cfg(not(target_os = "linux"))]
+    let focused = foreground_window;
+
+    loop {
+        if focused().is_some_and(|focused| focused.id == window.id) {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        sleep(FOCUS_POLL_INTERVAL).await;
+    }
+}
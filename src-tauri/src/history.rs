@@ -0,0 +1,269 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::path::Path;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::entry::ClipboardEntry;
+
+/// The file the panel opens through tauri-plugin-sql as `sqlite:clipboard_history.db`,
+/// which the plugin resolves against the app config directory.
+const DATABASE_FILE: &str = "clipboard_history.db";
+
+/// Backend access to the history database, for commands that refer to entries by id.
+pub struct History {
+    pool: SqlitePool,
+}
+
+/// A clipboard change to add to history, with the fields the panel shows.
+#[derive(Debug, Clone)]
+pub struct NewEntry {
+    pub content: String,
+    pub content_type: String,
+    pub preview: String,
+    pub metadata: String,
+    pub source_url: String,
+}
+
+/// Timestamps are stored as the panel writes them with `Date.toISOString()`,
+/// which sort as text.
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+pub fn init_history(app_handle: &AppHandle) -> Result<History, String> {
+    let dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| e.to_string())?;
+    // The daemon may run before the app ever has.
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+
+    // The pool spawns its maintenance task on creation, which needs a Tokio context.
+    let runtime = tauri::async_runtime::handle();
+    let _guard = runtime.inner().enter();
+
+    Ok(History::open(&dir.join(DATABASE_FILE)))
+}
+
+impl History {
+    /// Opens the database at `path`, creating the file if it doesn't exist yet.
+    fn open(path: &Path) -> History {
+        // The panel may be writing at the same time, so wait for its locks instead of failing.
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true)
+            .busy_timeout(Duration::from_secs(5));
+
+        History {
+            pool: SqlitePoolOptions::new()
+                .max_connections(2)
+                .connect_lazy_with(options),
+        }
+    }
+
+    /// Creates the entries table as the panel does in `db.ts`, so history can be
+    /// recorded before the panel has ever run.
+    pub async fn ensure_schema(&self) -> Result<(), String> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS clipboard_entries
+             (
+                 id              INTEGER PRIMARY KEY AUTOINCREMENT,
+                 content         TEXT    NOT NULL,
+                 content_type    TEXT    NOT NULL,
+                 preview         TEXT,
+                 copy_count      INTEGER NOT NULL DEFAULT 1,
+                 first_copied_at TEXT    NOT NULL,
+                 last_copied_at  TEXT    NOT NULL,
+                 is_favorite     BOOLEAN NOT NULL DEFAULT 0,
+                 metadata        TEXT,
+                 source_url      TEXT
+             )",
+        )
+        .execute(&self.pool)
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Failed to create history table: {}", e))
+    }
+
+    /// Adds `entry` to history. Content that is already there has its copy count
+    /// bumped and moves to the top instead. Returns the entry's id.
+    pub async fn record(&self, entry: &NewEntry) -> Result<i64, String> {
+        let content = entry.content.trim();
+        let now = timestamp(Utc::now());
+
+        let existing: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM clipboard_entries WHERE content = ? AND content_type = ?",
+        )
+        .bind(content)
+        .bind(&entry.content_type)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to look up entry: {}", e))?;
+
+        if let Some(id) = existing {
+            sqlx::query(
+                "UPDATE clipboard_entries SET copy_count = copy_count + 1, last_copied_at = ?
+                 WHERE id = ?",
+            )
+            .bind(&now)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to update entry {}: {}", id, e))?;
+            return Ok(id);
+        }
+
+        sqlx::query(
+            "INSERT INTO clipboard_entries
+             (content, content_type, preview, copy_count, first_copied_at, last_copied_at,
+              metadata, source_url)
+             VALUES (?, ?, ?, 1, ?, ?, ?, ?)",
+        )
+        .bind(content)
+        .bind(&entry.content_type)
+        .bind(&entry.preview)
+        .bind(&now)
+        .bind(&now)
+        .bind(&entry.metadata)
+        .bind(&entry.source_url)
+        .execute(&self.pool)
+        .await
+        .map(|result| result.last_insert_rowid())
+        .map_err(|e| format!("Failed to save entry: {}", e))
+    }
+
+    /// Loads the entries with `ids`, keeping the order of `ids`.
+    pub async fn entries(&self, ids: &[i64]) -> Result<Vec<ClipboardEntry>, String> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sql = format!(
+            "SELECT id, content, content_type, preview FROM clipboard_entries WHERE id IN ({})",
+            vec!["?"; ids.len()].join(", ")
+        );
+        let mut query = sqlx::query_as::<_, ClipboardEntry>(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        let found = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to load entries: {}", e))?;
+
+        ids.iter()
+            .map(|id| {
+                found
+                    .iter()
+                    .find(|entry| entry.id == *id)
+                    .cloned()
+                    .ok_or_else(|| format!("Entry {} no longer exists", id))
+            })
+            .collect()
+    }
+
+    /// The `limit` most recently copied entries, newest first.
+    pub async fn recent(&self, limit: u32) -> Result<Vec<ClipboardEntry>, String> {
+        sqlx::query_as::<_, ClipboardEntry>(
+            "SELECT id, content, content_type, preview FROM clipboard_entries
+             ORDER BY last_copied_at DESC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load recent entries: {}", e))
+    }
+
+    /// Deletes all entries, except favorites if `keep_favorites` is set.
+    pub async fn clear(&self, keep_favorites: bool) -> Result<(), String> {
+        let sql = if keep_favorites {
+            "DELETE FROM clipboard_entries WHERE is_favorite = 0"
+        } else {
+            "DELETE FROM clipboard_entries"
+        };
+        sqlx::query(sql)
+            .execute(&self.pool)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Failed to clear history: {}", e))
+    }
+
+    /// Deletes entries last copied more than `max_age_days` ago and all but the
+    /// `max_entries` most recent ones. Favorites are never deleted and don't count
+    /// toward `max_entries`. Returns the number of entries deleted.
+    pub async fn prune(
+        &self,
+        max_entries: Option<u32>,
+        max_age_days: Option<u32>,
+    ) -> Result<u64, String> {
+        let mut deleted = 0;
+
+        if let Some(days) = max_age_days {
+            let cutoff = timestamp(Utc::now() - chrono::Duration::days(days.into()));
+            deleted += sqlx::query(
+                "DELETE FROM clipboard_entries WHERE is_favorite = 0 AND last_copied_at < ?",
+            )
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete old entries: {}", e))?
+            .rows_affected();
+        }
+
+        if let Some(limit) = max_entries {
+            deleted += sqlx::query(
+                "DELETE FROM clipboard_entries WHERE is_favorite = 0 AND id NOT IN
+                 (SELECT id FROM clipboard_entries WHERE is_favorite = 0
+                  ORDER BY last_copied_at DESC LIMIT ?)",
+            )
+            .bind(limit)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete excess entries: {}", e))?
+            .rows_affected();
+        }
+
+        Ok(deleted)
+    }
+
+    /// The key/value pairs in the panel's `settings` table.
+    pub async fn settings(&self) -> Result<Vec<(String, String)>, String> {
+        sqlx::query_as::<_, (String, String)>("SELECT key, value FROM settings")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to load settings: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn records_into_a_database_that_does_not_exist_yet() {
+        let dir = std::env::temp_dir().join(format!("nabu-history-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let history = History::open(&dir.join(DATABASE_FILE));
+        history.ensure_schema().await.unwrap();
+        let id = history
+            .record(&NewEntry {
+                content: "hello".into(),
+                content_type: "text".into(),
+                preview: "hello".into(),
+                metadata: String::new(),
+                source_url: String::new(),
+            })
+            .await
+            .unwrap();
+        let entries = history.entries(&[id]).await.unwrap();
+        let created = dir.join(DATABASE_FILE).exists();
+        history.pool.close().await;
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(created);
+        assert_eq!(entries[0].content, "hello");
+    }
+}
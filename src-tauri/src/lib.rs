@@ -1,24 +1,56 @@
-use tauri::Manager;
+use tauri::{Manager, WebviewWindowBuilder};
 use tauri_plugin_autostart::MacosLauncher;
 use url_preview::PreviewService;
 
+mod capture;
 mod clipboard_metadata;
+mod clipboard_snapshot;
+mod config;
+mod daemon;
+mod editor;
+mod entry;
+mod external_command;
+mod foreground;
+mod history;
+mod macros;
+mod paste;
+mod paste_queue;
+mod pinned;
+mod placement;
+mod plain_text;
+mod recorder;
+mod scripts;
+mod settings;
+mod settings_window;
 mod shortcuts;
+mod snippets;
+mod transforms;
 mod tray;
+mod typing;
 mod visibility;
 mod win_shortcut;
+mod window_geometry;
 
 #[cfg(target_os = "macos")]
 mod panel;
 
+fn context() -> tauri::Context<tauri::Wry> {
+    tauri::generate_context!()
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    if daemon::is_daemon_mode() {
+        return daemon::run(context());
+    }
+
     #[cfg_attr(not(target_os = "macos"), allow(unused_mut))]
     let mut builder = tauri::Builder::default()
         .plugin(tauri_plugin_autostart::init(
             MacosLauncher::LaunchAgent,
             None,
         ))
+        .plugin(tauri_plugin_log::Builder::new().build())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_prevent_default::debug());
 
@@ -31,10 +63,35 @@ pub fn run() {
         .setup(|app| {
             let app_handle = app.handle();
 
+            // Created here rather than from the config, which the daemon shares.
+            WebviewWindowBuilder::from_config(app_handle, &app.config().app.windows[0])?.build()?;
+
             app.manage(shortcuts::init_shortcut_state());
+            app.manage(paste::init_paste_rules());
+            app.manage(typing::init_typing_state());
+            app.manage(clipboard_snapshot::init_clipboard_snapshot());
+            app.manage(foreground::init_focus_state());
+            foreground::track_previous_window(&app_handle);
+            app.manage(paste_queue::init_paste_queue());
+            app.manage(macros::init_macro_state());
+            app.manage(history::init_history(&app_handle)?);
+            app.manage(settings::init_settings(&app_handle));
+            settings::sync_auto_start(&app_handle);
+            app.manage(config::init_config_state());
+            paste::sync_paste_rules(&app_handle);
+            app.manage(visibility::init_visibility_state());
+            app.manage(placement::init_placement_state());
+            app.manage(pinned::init_pinned_notes());
+            app.manage(capture::init_capture_state());
+            app.manage(recorder::init_recorder());
+            app.manage(tray::init_tray_state());
             tray::setup_tray(app)?;
             shortcuts::setup_shortcut_handler(&app_handle)?;
+            config::watch_config(&app_handle);
+            daemon::attach_or_record(&app_handle);
             app.manage(PreviewService::new());
+            let _ = window_geometry::restore_geometry(&app_handle);
+            pinned::restore_pinned_notes(&app_handle);
 
             #[cfg(target_os = "macos")] // Hide app icon in Dock
             {
@@ -43,6 +100,9 @@ pub fn run() {
                 panel::setup_panel(app);
             }
 
+            #[cfg(not(target_os = "macos"))]
+            visibility::setup_hide_on_blur(&app_handle);
+
             Ok(())
         })
         .plugin(tauri_plugin_sql::Builder::new().build())
@@ -50,14 +110,58 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![
             shortcuts::change_shortcut,
+            shortcuts::set_global_shortcut,
             clipboard_metadata::get_foreground_window_title,
             clipboard_metadata::get_clipboard_source_url,
             clipboard_metadata::generate_url_preview,
-            clipboard_metadata::paste,
+            paste::paste,
+            paste::paste_plain,
+            paste::paste_transformed,
+            transforms::transform_entry,
+            scripts::list_script_transforms,
+            scripts::run_script_transform,
+            scripts::paste_script_transform,
+            external_command::run_external_command,
+            external_command::paste_external_command,
+            editor::edit_entry_externally,
+            settings::get_settings,
+            settings::update_settings,
+            config::get_config_status,
+            config::open_config_file,
+            settings_window::open_settings,
+            paste::paste_joined,
+            paste::copy_joined,
+            paste_queue::enqueue_entries,
+            paste_queue::remove_queued_entry,
+            paste_queue::clear_paste_queue,
+            paste_queue::get_paste_queue,
+            paste_queue::paste_next,
+            pinned::pin_entry,
+            pinned::unpin_entry,
+            pinned::get_pinned_entry,
+            pinned::copy_pinned_entry,
+            pinned::paste_pinned_entry,
+            snippets::snippet_inputs,
+            snippets::expand_snippet,
+            snippets::paste_snippet,
+            macros::run_macro,
+            clipboard_snapshot::snapshot_clipboard,
+            clipboard_snapshot::abort_paste,
+            capture::set_capture_paused,
+            capture::is_capture_paused,
+            paste::get_paste_rules,
+            typing::paste_typed,
+            typing::cancel_typing,
             win_shortcut::toggle_windows_shortcut,
             win_shortcut::get_windows_shortcut_status,
             win_shortcut::is_admin,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(context())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::ExitRequested { .. } = event {
+                window_geometry::save_geometry(app_handle);
+                pinned::save_pinned_notes(app_handle);
+            }
+        });
 }
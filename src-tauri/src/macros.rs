@@ -0,0 +1,212 @@
+use enigo::Key;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{command, AppHandle, Manager};
+use tokio::time::sleep;
+
+use crate::clipboard_snapshot::ClipboardSnapshot;
+use crate::entry::ClipboardEntry;
+use crate::foreground;
+use crate::paste::{chord, send_paste, with_enigo, PasteError, PasteRules};
+use crate::settings::SettingsStore;
+
+const DEFAULT_STEP_DELAY_MS: u64 = 150;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MacroStep {
+    Paste {
+        entry: ClipboardEntry,
+    },
+    Key {
+        key: String,
+        #[serde(default)]
+        modifiers: Vec<String>,
+    },
+    Wait {
+        ms: u64,
+    },
+}
+
+/// A named sequence of pastes and key presses, e.g. for filling in a form. Kept in
+/// the settings store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasteMacro {
+    pub id: u32,
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+    #[serde(default = "default_step_delay_ms")]
+    pub step_delay_ms: u64,
+}
+
+fn default_step_delay_ms() -> u64 {
+    DEFAULT_STEP_DELAY_MS
+}
+
+impl PasteMacro {
+    /// Checks that every key step names a key and modifiers the macro can press.
+    pub fn validate(&self) -> Result<(), String> {
+        for step in &self.steps {
+            if let MacroStep::Key { key, modifiers } = step {
+                parse_input_key(key)?;
+                for modifier in modifiers {
+                    parse_input_modifier(modifier)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct MacroState {
+    running: AtomicBool,
+}
+
+pub fn init_macro_state() -> MacroState {
+    MacroState {
+        running: AtomicBool::new(false),
+    }
+}
+
+/// Runs the macro with `id` against the window the panel was opened from.
+pub async fn run_macro_by_id(
+    app: &AppHandle,
+    id: u32,
+    restore_clipboard: bool,
+) -> Result<(), PasteError> {
+    let state = app.state::<MacroState>();
+    let paste_macro = app
+        .state::<SettingsStore>()
+        .get()
+        .paste_macros
+        .into_iter()
+        .find(|paste_macro| paste_macro.id == id)
+        .ok_or_else(|| format!("No macro with id {}", id))?;
+
+    if state.running.swap(true, Ordering::SeqCst) {
+        return Err("Another macro is already running".to_string().into());
+    }
+
+    let result = match restore_clipboard {
+        true => app
+            .state::<ClipboardSnapshot>()
+            .take()
+            .map_err(PasteError::from),
+        false => Ok(()),
+    };
+    let result = match result {
+        Ok(()) => run_steps(app, &paste_macro).await,
+        Err(e) => Err(e),
+    };
+
+    let finished = crate::clipboard_snapshot::finish_paste(app, restore_clipboard).await;
+    state.running.store(false, Ordering::SeqCst);
+
+    result.and(finished.map_err(PasteError::from))
+}
+
+async fn run_steps(app: &AppHandle, paste_macro: &PasteMacro) -> Result<(), PasteError> {
+    let target = crate::paste::restore_focus(app).await?;
+    let strategy = app.state::<PasteRules>().strategy_for(target.as_ref());
+    let step_delay = Duration::from_millis(paste_macro.step_delay_ms);
+
+    for (index, step) in paste_macro.steps.iter().enumerate() {
+        if index > 0 {
+            sleep(step_delay).await;
+        }
+
+        if let Some(target) = &target {
+            let still_focused =
+                foreground::foreground_window().is_some_and(|focused| focused.id == target.id);
+            if !still_focused {
+                return Err(PasteError::FocusLost {
+                    window: target.describe(),
+                });
+            }
+        }
+
+        match step {
+            MacroStep::Paste { entry } => {
+                entry.write_to_clipboard()?;
+                send_paste(app, strategy).await?;
+            }
+            MacroStep::Key { key, modifiers } => {
+                let key = parse_input_key(key)?;
+                let modifiers = modifiers
+                    .iter()
+                    .map(|modifier| parse_input_modifier(modifier))
+                    .collect::<Result<Vec<_>, _>>()?;
+                with_enigo(app, move |enigo| {
+                    chord(enigo, &modifiers, key).map_err(|e| e.to_string())
+                })
+                .await?;
+            }
+            MacroStep::Wait { ms } => sleep(Duration::from_millis(*ms)).await,
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_input_modifier(modifier: &str) -> Result<Key, String> {
+    match modifier {
+        "ctrl" => Ok(Key::Control),
+        "shift" => Ok(Key::Shift),
+        "alt" => Ok(Key::Alt),
+        "meta" => Ok(Key::Meta),
+        _ => Err(format!("Unsupported modifier: {}", modifier)),
+    }
+}
+
+/// Maps a `KeyboardEvent.code` name, as recorded by the shortcut recorder, to a key press.
+fn parse_input_key(key: &str) -> Result<Key, String> {
+    if let Some(letter) = key.strip_prefix("Key").filter(|rest| rest.len() == 1) {
+        return Ok(Key::Unicode(
+            letter.to_ascii_lowercase().chars().next().unwrap(),
+        ));
+    }
+    if let Some(digit) = key.strip_prefix("Digit").filter(|rest| rest.len() == 1) {
+        return Ok(Key::Unicode(digit.chars().next().unwrap()));
+    }
+
+    match key {
+        "Enter" | "NumpadEnter" => Ok(Key::Return),
+        "Tab" => Ok(Key::Tab),
+        "Escape" => Ok(Key::Escape),
+        "Space" => Ok(Key::Space),
+        "Backspace" => Ok(Key::Backspace),
+        "Delete" => Ok(Key::Delete),
+        "ArrowUp" => Ok(Key::UpArrow),
+        "ArrowDown" => Ok(Key::DownArrow),
+        "ArrowLeft" => Ok(Key::LeftArrow),
+        "ArrowRight" => Ok(Key::RightArrow),
+        "Home" => Ok(Key::Home),
+        "End" => Ok(Key::End),
+        "PageUp" => Ok(Key::PageUp),
+        "PageDown" => Ok(Key::PageDown),
+        "F1" => Ok(Key::F1),
+        "F2" => Ok(Key::F2),
+        "F3" => Ok(Key::F3),
+        "F4" => Ok(Key::F4),
+        "F5" => Ok(Key::F5),
+        "F6" => Ok(Key::F6),
+        "F7" => Ok(Key::F7),
+        "F8" => Ok(Key::F8),
+        "F9" => Ok(Key::F9),
+        "F10" => Ok(Key::F10),
+        "F11" => Ok(Key::F11),
+        "F12" => Ok(Key::F12),
+        _ => Err(format!("Unsupported key: {}", key)),
+    }
+}
+
+#[command]
+pub async fn run_macro(
+    app: AppHandle,
+    id: u32,
+    restore_clipboard: Option<bool>,
+) -> Result<(), PasteError> {
+    run_macro_by_id(&app, id, restore_clipboard.unwrap_or(false)).await
+}
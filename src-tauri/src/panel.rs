@@ -31,7 +31,9 @@ pub fn setup_panel(app: &App) {
             let app_handle = app.app_handle().clone();
 
             handler.window_did_resign_key(move |_| {
-                let _ = crate::visibility::hide_panel(&app_handle);
+                if crate::visibility::should_hide_on_blur(&app_handle) {
+                    let _ = crate::visibility::hide_panel(&app_handle);
+                }
             });
 
             panel.set_event_handler(Some(handler.as_ref()));
@@ -0,0 +1,391 @@
+use clipboard_rs::{Clipboard, ClipboardContext};
+use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{command, AppHandle, Manager, State};
+use tokio::sync::oneshot;
+use tokio::time::sleep;
+
+use crate::clipboard_snapshot::ClipboardSnapshot;
+use crate::config::ConfigState;
+use crate::entry::{join_plain_text, ClipboardEntry};
+use crate::foreground::{self, ForegroundWindow};
+use crate::history::History;
+use crate::plain_text::{html_to_text, rtf_to_text};
+use crate::settings::{on_settings_changed, SettingsStore};
+use crate::transforms::Transform;
+
+thread_local! {
+    static ENIGO: RefCell<Option<Enigo>> = const { RefCell::new(None) };
+}
+
+/// How a paste is delivered to the target application.
+///
+/// `CtrlV` and `CtrlShiftV` use Cmd instead of Ctrl on macOS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PasteStrategy {
+    CtrlV,
+    CtrlShiftV,
+    ShiftInsert,
+    TypeText,
+}
+
+/// Maps a process name or window class to a paste strategy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PasteRule {
+    pub app: String,
+    pub strategy: PasteStrategy,
+}
+
+pub struct PasteRules {
+    pub rules: Mutex<Vec<PasteRule>>,
+}
+
+impl PasteRules {
+    pub fn strategy_for(&self, window: Option<&ForegroundWindow>) -> PasteStrategy {
+        window
+            .and_then(|window| {
+                self.rules
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|rule| window.matches(&rule.app))
+                    .map(|rule| rule.strategy)
+            })
+            .unwrap_or(PasteStrategy::CtrlV)
+    }
+}
+
+pub fn default_paste_rules() -> Vec<PasteRule> {
+    let rule = |app: &str, strategy| PasteRule {
+        app: app.to_string(),
+        strategy,
+    };
+
+    vec![
+        rule("gnome-terminal-server", PasteStrategy::CtrlShiftV),
+        rule("kgx", PasteStrategy::CtrlShiftV),
+        rule("kitty", PasteStrategy::CtrlShiftV),
+        rule("alacritty", PasteStrategy::CtrlShiftV),
+        rule("konsole", PasteStrategy::CtrlShiftV),
+        rule("xfce4-terminal", PasteStrategy::CtrlShiftV),
+        rule("tilix", PasteStrategy::CtrlShiftV),
+        rule("terminator", PasteStrategy::CtrlShiftV),
+        rule("wezterm-gui", PasteStrategy::CtrlShiftV),
+        rule("urxvt", PasteStrategy::ShiftInsert),
+        rule("mintty", PasteStrategy::ShiftInsert),
+        rule("putty", PasteStrategy::ShiftInsert),
+    ]
+}
+
+pub fn init_paste_rules() -> PasteRules {
+    PasteRules {
+        rules: Mutex::new(default_paste_rules()),
+    }
+}
+
+/// Puts the rules in effect: the config file's, then the ones saved in the
+/// settings window, then the built-in ones for terminals.
+pub fn apply_paste_rules(app_handle: &AppHandle, config_rules: &[PasteRule], saved: &[PasteRule]) {
+    *app_handle.state::<PasteRules>().rules.lock().unwrap() = config_rules
+        .iter()
+        .chain(saved)
+        .cloned()
+        .chain(default_paste_rules())
+        .collect();
+}
+
+/// Keeps the rules in effect in line with `paste_rules` in the settings.
+pub fn sync_paste_rules(app_handle: &AppHandle) {
+    let config_rules = app_handle.state::<ConfigState>().get().paste_rules;
+    let saved = app_handle.state::<SettingsStore>().get().paste_rules;
+    apply_paste_rules(app_handle, &config_rules, &saved);
+
+    on_settings_changed(app_handle, |app_handle, settings| {
+        let config_rules = app_handle.state::<ConfigState>().get().paste_rules;
+        apply_paste_rules(app_handle, &config_rules, &settings.paste_rules);
+    });
+}
+
+/// The rules in effect, in the order they are checked.
+#[command]
+pub fn get_paste_rules(state: State<PasteRules>) -> Vec<PasteRule> {
+    state.rules.lock().unwrap().clone()
+}
+
+/// How long to wait for the window the panel was opened from to regain focus.
+const FOCUS_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Used instead of [`FOCUS_TIMEOUT`] when the previous window is unknown.
+const FOCUS_SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// Returned to the panel when a paste cannot be delivered.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum PasteError {
+    /// The window the panel was opened from never regained keyboard focus.
+    #[serde(rename_all = "camelCase")]
+    FocusNotRestored {
+        window: String,
+        waited_ms: u64,
+    },
+    /// Focus moved to another window part way through a macro.
+    FocusLost {
+        window: String,
+    },
+    Failed {
+        message: String,
+    },
+}
+
+impl From<String> for PasteError {
+    fn from(message: String) -> Self {
+        PasteError::Failed { message }
+    }
+}
+
+impl std::fmt::Display for PasteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PasteError::FocusNotRestored { window, waited_ms } => {
+                write!(f, "{} did not regain focus within {} ms", window, waited_ms)
+            }
+            PasteError::FocusLost { window } => write!(f, "{} lost focus", window),
+            PasteError::Failed { message } => f.write_str(message),
+        }
+    }
+}
+
+#[command]
+pub async fn paste(app: AppHandle, restore_clipboard: Option<bool>) -> Result<(), PasteError> {
+    paste_clipboard(&app, restore_clipboard.unwrap_or(false)).await
+}
+
+/// Pastes `entry`, or the current clipboard when no entry is given, without formatting.
+#[command]
+pub async fn paste_plain(
+    app: AppHandle,
+    entry: Option<ClipboardEntry>,
+    restore_clipboard: Option<bool>,
+) -> Result<(), PasteError> {
+    let restore_clipboard = restore_clipboard.unwrap_or(false);
+    let text = match entry {
+        Some(entry) => entry.plain_text()?,
+        None => clipboard_plain_text()?,
+    };
+
+    write_and_paste(&app, restore_clipboard, || write_plain_text(text)).await
+}
+
+/// Pastes `entry` as plain text after applying `transform`.
+#[command]
+pub async fn paste_transformed(
+    app: AppHandle,
+    entry: ClipboardEntry,
+    transform: Transform,
+    restore_clipboard: Option<bool>,
+) -> Result<(), PasteError> {
+    let restore_clipboard = restore_clipboard.unwrap_or(false);
+    let text = transform.apply(&entry.plain_text()?)?;
+
+    write_and_paste(&app, restore_clipboard, || write_plain_text(text)).await
+}
+
+/// Pastes several entries as one text, joined with `separator` in the order of `ids`.
+#[command]
+pub async fn paste_joined(
+    app: AppHandle,
+    ids: Vec<i64>,
+    separator: String,
+    restore_clipboard: Option<bool>,
+    history: State<'_, History>,
+) -> Result<(), PasteError> {
+    let restore_clipboard = restore_clipboard.unwrap_or(false);
+    let text = join_plain_text(&history.entries(&ids).await?, &separator)?;
+
+    write_and_paste(&app, restore_clipboard, || write_plain_text(text)).await
+}
+
+/// Copies several entries as one text, joined with `separator` in the order of `ids`.
+#[command]
+pub async fn copy_joined(
+    ids: Vec<i64>,
+    separator: String,
+    history: State<'_, History>,
+) -> Result<(), String> {
+    let text = join_plain_text(&history.entries(&ids).await?, &separator)?;
+    write_plain_text(text)
+}
+
+/// Pastes the current clipboard as plain text; used by the global shortcut.
+pub async fn paste_plain_clipboard(app: &AppHandle) -> Result<(), PasteError> {
+    let text = clipboard_plain_text()?;
+    write_and_paste(app, false, || write_plain_text(text)).await
+}
+
+/// Puts new content on the clipboard with `write` and pastes it. With
+/// `restore_clipboard`, the previous clipboard is saved first and put back
+/// afterwards, also when writing or pasting fails.
+pub async fn write_and_paste(
+    app: &AppHandle,
+    restore_clipboard: bool,
+    write: impl FnOnce() -> Result<(), String>,
+) -> Result<(), PasteError> {
+    if restore_clipboard {
+        app.state::<ClipboardSnapshot>().take()?;
+    }
+    if let Err(e) = write() {
+        let _ = crate::clipboard_snapshot::finish_paste(app, restore_clipboard).await;
+        return Err(e.into());
+    }
+    paste_clipboard(app, restore_clipboard).await
+}
+
+/// Pastes the current clipboard into the application the panel was opened from.
+pub async fn paste_clipboard(app: &AppHandle, restore_clipboard: bool) -> Result<(), PasteError> {
+    let result = match restore_focus(app).await {
+        Ok(target) => {
+            let strategy = app.state::<PasteRules>().strategy_for(target.as_ref());
+            send_paste(app, strategy).await.map_err(PasteError::from)
+        }
+        Err(e) => Err(e),
+    };
+
+    let finished = crate::clipboard_snapshot::finish_paste(app, restore_clipboard).await;
+    result.and(finished.map_err(PasteError::from))
+}
+
+pub fn clipboard_plain_text() -> Result<String, String> {
+    let clipboard = ClipboardContext::new().map_err(|e| e.to_string())?;
+
+    clipboard
+        .get_text()
+        .ok()
+        .filter(|text| !text.is_empty())
+        .or_else(|| clipboard.get_html().ok().map(|html| html_to_text(&html)))
+        .or_else(|| clipboard.get_rich_text().ok().map(|rtf| rtf_to_text(&rtf)))
+        .ok_or_else(|| "The clipboard has no text to paste".to_string())
+}
+
+pub fn write_plain_text(text: String) -> Result<(), String> {
+    ClipboardContext::new()
+        .and_then(|clipboard| clipboard.set_text(text))
+        .map_err(|e| format!("Failed to write plain text to clipboard: {}", e))
+}
+
+/// Hides the panel and waits until the window it was opened from has focus
+/// again. Returns the window that will receive the paste.
+pub async fn restore_focus(app: &AppHandle) -> Result<Option<ForegroundWindow>, PasteError> {
+    let (tx, rx) = oneshot::channel();
+    let app_clone = app.clone();
+    app.run_on_main_thread(move || {
+        let was_visible = crate::visibility::is_panel_visible(&app_clone);
+        let _ = crate::visibility::hide_panel(&app_clone);
+        let _ = tx.send(was_visible);
+    })
+    .map_err(|e| e.to_string())?;
+
+    // Pastes triggered by a global shortcut go to whatever is focused right now,
+    // unless that is Nabu itself, e.g. the tray menu that was just clicked.
+    if !rx.await.map_err(|e| e.to_string())? {
+        let focused = foreground::foreground_window();
+        if !focused.as_ref().is_some_and(ForegroundWindow::is_own) {
+            return Ok(focused);
+        }
+    }
+
+    // Hiding the panel doesn't guarantee focus goes back to where it came from,
+    // e.g. under tiling window managers or across monitors.
+    refocus_previous_window(app).await
+}
+
+/// Gives focus back to the window recorded by `remember_previous_window` and
+/// waits until it has it. Returns the window that will receive the paste.
+async fn refocus_previous_window(app: &AppHandle) -> Result<Option<ForegroundWindow>, PasteError> {
+    match foreground::previous_window(app) {
+        Some(window) => {
+            let _ = foreground::activate_window(&window);
+
+            if foreground::wait_for_focus(&window, FOCUS_TIMEOUT).await {
+                Ok(Some(window))
+            } else {
+                Err(PasteError::FocusNotRestored {
+                    window: window.describe(),
+                    waited_ms: FOCUS_TIMEOUT.as_millis() as u64,
+                })
+            }
+        }
+        // Focus can't be observed here: macOS keeps the previous app active behind
+        // the non-activating panel, and Wayland doesn't expose the active window.
+        None => {
+            sleep(FOCUS_SETTLE_DELAY).await;
+            Ok(foreground::foreground_window())
+        }
+    }
+}
+
+/// Delivers the current clipboard to the focused application using `strategy`.
+pub async fn send_paste(app: &AppHandle, strategy: PasteStrategy) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let modifier = Key::Meta;
+
+    #[cfg(not(target_os = "macos"))]
+    let modifier = Key::Control;
+
+    let (modifiers, key) = match strategy {
+        PasteStrategy::CtrlV => (vec![modifier], Key::Unicode('v')),
+        PasteStrategy::CtrlShiftV => (vec![modifier, Key::Shift], Key::Unicode('v')),
+        #[cfg(not(target_os = "macos"))]
+        PasteStrategy::ShiftInsert => (vec![Key::Shift], Key::Insert),
+        // Mac keyboards have no Insert key, so fall back to the regular shortcut.
+        #[cfg(target_os = "macos")]
+        PasteStrategy::ShiftInsert => (vec![modifier], Key::Unicode('v')),
+        PasteStrategy::TypeText => {
+            let text = ClipboardContext::new()
+                .and_then(|clipboard| clipboard.get_text())
+                .map_err(|e| format!("Failed to read clipboard text: {}", e))?;
+            return crate::typing::type_text(app, &text).await;
+        }
+    };
+
+    with_enigo(app, move |enigo| {
+        chord(enigo, &modifiers, key).map_err(|e| e.to_string())
+    })
+    .await
+}
+
+/// Runs `f` on the main thread with an input simulator that is reused across calls.
+pub async fn with_enigo<T, F>(app: &AppHandle, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut Enigo) -> Result<T, String> + Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    app.run_on_main_thread(move || {
+        let result = ENIGO.with(|cell| {
+            let mut enigo = cell.borrow_mut();
+            if enigo.is_none() {
+                *enigo = Some(Enigo::new(&Settings::default()).map_err(|e| e.to_string())?);
+            }
+            f(enigo.as_mut().unwrap())
+        });
+        let _ = tx.send(result);
+    })
+    .map_err(|e| e.to_string())?;
+
+    rx.await.map_err(|e| e.to_string())?
+}
+
+pub fn chord(enigo: &mut Enigo, modifiers: &[Key], key: Key) -> enigo::InputResult<()> {
+    for modifier in modifiers {
+        enigo.key(*modifier, Direction::Press)?;
+    }
+    let result = enigo.key(key, Direction::Click);
+    for modifier in modifiers.iter().rev() {
+        enigo.key(*modifier, Direction::Release)?;
+    }
+    result
+}
@@ -0,0 +1,93 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Emitter, Manager, State};
+
+use crate::entry::ClipboardEntry;
+use crate::paste::PasteError;
+
+/// Entries waiting to be pasted one at a time with "paste next".
+pub struct PasteQueue {
+    entries: Mutex<VecDeque<ClipboardEntry>>,
+}
+
+impl PasteQueue {
+    pub fn entries(&self) -> Vec<ClipboardEntry> {
+        self.entries.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn next(&self) -> Option<ClipboardEntry> {
+        self.entries.lock().unwrap().front().cloned()
+    }
+}
+
+pub fn init_paste_queue() -> PasteQueue {
+    PasteQueue {
+        entries: Mutex::new(VecDeque::new()),
+    }
+}
+
+fn notify_changed(app: &AppHandle) {
+    let entries = app.state::<PasteQueue>().entries();
+    let _ = app.emit("paste-queue-changed", &entries);
+    crate::tray::update_tooltip(app);
+}
+
+/// Pastes the head of the queue and advances it once the paste succeeds.
+pub async fn paste_next_entry(app: &AppHandle) -> Result<(), PasteError> {
+    let entry = app
+        .state::<PasteQueue>()
+        .next()
+        .ok_or_else(|| "The paste queue is empty".to_string())?;
+
+    entry.write_to_clipboard()?;
+    crate::paste::paste_clipboard(app, false).await?;
+
+    {
+        let queue = app.state::<PasteQueue>();
+        let mut entries = queue.entries.lock().unwrap();
+        if entries.front().is_some_and(|front| front.id == entry.id) {
+            entries.pop_front();
+        }
+    }
+    notify_changed(app);
+
+    Ok(())
+}
+
+#[command]
+pub fn enqueue_entries(
+    app: AppHandle,
+    entries: Vec<ClipboardEntry>,
+    state: State<PasteQueue>,
+) -> Vec<ClipboardEntry> {
+    state.entries.lock().unwrap().extend(entries);
+    notify_changed(&app);
+    state.entries()
+}
+
+#[command]
+pub fn remove_queued_entry(
+    app: AppHandle,
+    index: usize,
+    state: State<PasteQueue>,
+) -> Vec<ClipboardEntry> {
+    state.entries.lock().unwrap().remove(index);
+    notify_changed(&app);
+    state.entries()
+}
+
+#[command]
+pub fn clear_paste_queue(app: AppHandle, state: State<PasteQueue>) {
+    state.entries.lock().unwrap().clear();
+    notify_changed(&app);
+}
+
+#[command]
+pub fn get_paste_queue(state: State<PasteQueue>) -> Vec<ClipboardEntry> {
+    state.entries()
+}
+
+#[command]
+pub async fn paste_next(app: AppHandle) -> Result<(), PasteError> {
+    paste_next_entry(&app).await
+}
@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{
+    command, AppHandle, Manager, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+    WindowEvent,
+};
+use tokio::time::sleep;
+
+use crate::entry::ClipboardEntry;
+use crate::paste::{write_and_paste, PasteError};
+
+const PINNED_FILE: &str = "pinned_notes.json";
+
+/// Pinned note windows are labelled `pinned-<entry id>`, which the frontend
+/// uses to render the note instead of the panel.
+const LABEL_PREFIX: &str = "pinned-";
+
+const DEFAULT_WIDTH: f64 = 320.0;
+const DEFAULT_HEIGHT: f64 = 180.0;
+const TITLE_PREVIEW_CHARS: usize = 40;
+/// How long a note has to stay put after being moved or resized before its
+/// geometry is written to disk.
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// An entry shown in its own always-on-top window, with that window's geometry
+/// in logical pixels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PinnedNote {
+    entry: ClipboardEntry,
+    x: Option<f64>,
+    y: Option<f64>,
+    width: f64,
+    height: f64,
+}
+
+/// Open pinned notes, keyed by window label.
+pub struct PinnedNotes {
+    notes: Mutex<HashMap<String, PinnedNote>>,
+    save_generation: AtomicU64,
+}
+
+pub fn init_pinned_notes() -> PinnedNotes {
+    PinnedNotes {
+        notes: Mutex::new(HashMap::new()),
+        save_generation: AtomicU64::new(0),
+    }
+}
+
+fn pinned_path(app_handle: &AppHandle) -> Option<PathBuf> {
+    app_handle
+        .path()
+        .app_config_dir()
+        .ok()
+        .map(|dir| dir.join(PINNED_FILE))
+}
+
+/// Writes the open notes to disk, so they reopen on the next start.
+pub fn save_pinned_notes(app_handle: &AppHandle) {
+    let Some(path) = pinned_path(app_handle) else {
+        return;
+    };
+    let notes: Vec<PinnedNote> = app_handle
+        .state::<PinnedNotes>()
+        .notes
+        .lock()
+        .unwrap()
+        .values()
+        .cloned()
+        .collect();
+
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&notes) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+/// Saves the notes once they stop changing, so dragging a note doesn't write
+/// the file on every step.
+fn schedule_save(app_handle: &AppHandle) {
+    let generation = app_handle
+        .state::<PinnedNotes>()
+        .save_generation
+        .fetch_add(1, Ordering::SeqCst)
+        + 1;
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        sleep(SAVE_DELAY).await;
+        let latest = app_handle
+            .state::<PinnedNotes>()
+            .save_generation
+            .load(Ordering::SeqCst);
+        if latest == generation {
+            save_pinned_notes(&app_handle);
+        }
+    });
+}
+
+/// Reopens the notes that were pinned when the app last quit.
+pub fn restore_pinned_notes(app_handle: &AppHandle) {
+    let notes: Vec<PinnedNote> = pinned_path(app_handle)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    for note in notes {
+        let _ = open_note_window(app_handle, note);
+    }
+}
+
+fn note_label(entry_id: i64) -> String {
+    format!("{}{}", LABEL_PREFIX, entry_id)
+}
+
+fn open_note_window(app_handle: &AppHandle, note: PinnedNote) -> tauri::Result<WebviewWindow> {
+    let label = note_label(note.entry.id);
+    let mut builder = WebviewWindowBuilder::new(app_handle, &label, WebviewUrl::default())
+        .title(note.entry.summary(TITLE_PREVIEW_CHARS))
+        .inner_size(note.width, note.height)
+        .min_inner_size(160.0, 80.0)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .resizable(true)
+        // Clicking the note's buttons must leave focus in the app being typed into,
+        // so paste goes there.
+        .focusable(false);
+    if let (Some(x), Some(y)) = (note.x, note.y) {
+        builder = builder.position(x, y);
+    }
+    let window = builder.build()?;
+
+    app_handle
+        .state::<PinnedNotes>()
+        .notes
+        .lock()
+        .unwrap()
+        .insert(label.clone(), note);
+
+    let app_handle = app_handle.clone();
+    let tracked = window.clone();
+    window.on_window_event(move |event| {
+        let notes = app_handle.state::<PinnedNotes>();
+        let mut notes = notes.notes.lock().unwrap();
+        let scale_factor = tracked.scale_factor().unwrap_or(1.0);
+
+        match event {
+            WindowEvent::Moved(position) => {
+                if let Some(note) = notes.get_mut(&label) {
+                    let position = position.to_logical::<f64>(scale_factor);
+                    note.x = Some(position.x);
+                    note.y = Some(position.y);
+                    schedule_save(&app_handle);
+                }
+            }
+            WindowEvent::Resized(size) => {
+                if let Some(note) = notes.get_mut(&label) {
+                    let size = size.to_logical::<f64>(scale_factor);
+                    note.width = size.width;
+                    note.height = size.height;
+                    schedule_save(&app_handle);
+                }
+            }
+            // Not saved here: on quit, the notes are saved before their windows close.
+            WindowEvent::Destroyed => {
+                notes.remove(&label);
+            }
+            _ => {}
+        }
+    });
+
+    Ok(window)
+}
+
+fn pinned_entry(window: &WebviewWindow, state: &PinnedNotes) -> Result<ClipboardEntry, String> {
+    state
+        .notes
+        .lock()
+        .unwrap()
+        .get(window.label())
+        .map(|note| note.entry.clone())
+        .ok_or_else(|| format!("{} is not a pinned note", window.label()))
+}
+
+/// Opens `entry` in a small always-on-top window, or focuses it if it is already pinned.
+#[command]
+pub fn pin_entry(app: AppHandle, entry: ClipboardEntry) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(&note_label(entry.id)) {
+        return window.show().map_err(|e| e.to_string());
+    }
+
+    open_note_window(
+        &app,
+        PinnedNote {
+            entry,
+            x: None,
+            y: None,
+            width: DEFAULT_WIDTH,
+            height: DEFAULT_HEIGHT,
+        },
+    )
+    .map_err(|e| format!("Failed to open pinned note: {}", e))?;
+    save_pinned_notes(&app);
+    Ok(())
+}
+
+/// Closes the calling note window.
+#[command]
+pub fn unpin_entry(
+    app: AppHandle,
+    window: WebviewWindow,
+    state: State<PinnedNotes>,
+) -> Result<(), String> {
+    state.notes.lock().unwrap().remove(window.label());
+    save_pinned_notes(&app);
+    window.close().map_err(|e| e.to_string())
+}
+
+/// The entry shown by the calling note window.
+#[command]
+pub fn get_pinned_entry(
+    window: WebviewWindow,
+    state: State<PinnedNotes>,
+) -> Result<ClipboardEntry, String> {
+    pinned_entry(&window, &state)
+}
+
+#[command]
+pub fn copy_pinned_entry(window: WebviewWindow, state: State<PinnedNotes>) -> Result<(), String> {
+    pinned_entry(&window, &state)?.write_to_clipboard()
+}
+
+/// Pastes the calling note's entry into the focused application.
+#[command]
+pub async fn paste_pinned_entry(
+    app: AppHandle,
+    window: WebviewWindow,
+    restore_clipboard: Option<bool>,
+    state: State<'_, PinnedNotes>,
+) -> Result<(), PasteError> {
+    let restore_clipboard = restore_clipboard.unwrap_or(false);
+    let entry = pinned_entry(&window, &state)?;
+
+    write_and_paste(&app, restore_clipboard, || entry.write_to_clipboard()).await
+}
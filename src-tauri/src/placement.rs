@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Monitor, PhysicalPosition, PhysicalSize};
+
+use crate::settings::SettingsStore;
+
+/// Where the panel appears when it is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PanelPlacement {
+    /// Centered on the primary monitor.
+    Center,
+    /// With its top-left corner at the mouse cursor.
+    AtCursor,
+    /// Centered on the monitor the mouse cursor is on.
+    CursorMonitor,
+    /// Wherever it was last hidden.
+    #[default]
+    LastPosition,
+}
+
+pub struct PlacementState {
+    last_position: Mutex<Option<PhysicalPosition<i32>>>,
+}
+
+pub fn init_placement_state() -> PlacementState {
+    PlacementState {
+        last_position: Mutex::new(None),
+    }
+}
+
+/// Records where the panel is, for [`PanelPlacement::LastPosition`].
+pub fn remember_position(app_handle: &AppHandle) {
+    if let Some(position) = app_handle
+        .get_webview_window("main")
+        .and_then(|window| window.outer_position().ok())
+    {
+        *app_handle
+            .state::<PlacementState>()
+            .last_position
+            .lock()
+            .unwrap() = Some(position);
+    }
+}
+
+/// Moves the panel according to the placement setting. Called before it is shown.
+pub fn position_panel(app_handle: &AppHandle) -> tauri::Result<()> {
+    let Some(window) = app_handle.get_webview_window("main") else {
+        return Ok(());
+    };
+    let placement = app_handle.state::<SettingsStore>().get().panel_placement;
+    let last_position = *app_handle
+        .state::<PlacementState>()
+        .last_position
+        .lock()
+        .unwrap();
+
+    let primary_monitor = app_handle.primary_monitor()?;
+    let cursor_monitor = || -> tauri::Result<_> {
+        let cursor = app_handle.cursor_position()?;
+        let monitor = app_handle.monitor_from_point(cursor.x, cursor.y)?;
+        Ok((
+            monitor,
+            PhysicalPosition::new(cursor.x as i32, cursor.y as i32),
+        ))
+    };
+
+    let (monitor, anchor) = match placement {
+        PanelPlacement::Center => (primary_monitor, None),
+        PanelPlacement::CursorMonitor => (cursor_monitor()?.0.or(primary_monitor), None),
+        PanelPlacement::AtCursor => {
+            let (monitor, cursor) = cursor_monitor()?;
+            (monitor.or(primary_monitor), Some(cursor))
+        }
+        PanelPlacement::LastPosition => match last_position {
+            Some(position) => {
+                // Fall back to the primary monitor if the remembered one is gone.
+                let monitor = app_handle
+                    .monitor_from_point(position.x as f64, position.y as f64)?
+                    .or(primary_monitor);
+                (monitor, Some(position))
+            }
+            None => return Ok(()),
+        },
+    };
+    let Some(monitor) = monitor else {
+        return Ok(());
+    };
+
+    // The window's physical size changes with the scale factor of the monitor it
+    // ends up on, so measure it in the target monitor's pixels.
+    let size = window
+        .outer_size()?
+        .to_logical::<f64>(window.scale_factor()?)
+        .to_physical::<u32>(monitor.scale_factor());
+
+    let position = match anchor {
+        Some(anchor) => clamp_to_work_area(&monitor, anchor, size),
+        None => center_in_work_area(&monitor, size),
+    };
+    window.set_position(position)
+}
+
+fn center_in_work_area(monitor: &Monitor, size: PhysicalSize<u32>) -> PhysicalPosition<i32> {
+    let area = monitor.work_area();
+    let centered = PhysicalPosition::new(
+        area.position.x + (area.size.width as i32 - size.width as i32) / 2,
+        area.position.y + (area.size.height as i32 - size.height as i32) / 2,
+    );
+    clamp_to_work_area(monitor, centered, size)
+}
+
+/// Moves `position` so a window of `size` there lies inside the monitor's work
+/// area, keeping the top-left corner visible if the window is larger than it.
+pub fn clamp_to_work_area(
+    monitor: &Monitor,
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+) -> PhysicalPosition<i32> {
+    let area = monitor.work_area();
+    let max_x = area.position.x + area.size.width as i32 - size.width as i32;
+    let max_y = area.position.y + area.size.height as i32 - size.height as i32;
+
+    PhysicalPosition::new(
+        position.x.min(max_x).max(area.position.x),
+        position.y.min(max_y).max(area.position.y),
+    )
+}
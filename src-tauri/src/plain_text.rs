@@ -0,0 +1,526 @@
+//! Plain-text renditions of HTML and RTF clipboard content.
+
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "blockquote",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+];
+
+const SKIPPED_HTML_TAGS: &[&str] = &["head", "script", "style", "template", "title"];
+
+pub fn html_to_text(html: &str) -> String {
+    // Windows clipboard HTML wraps the copied fragment in a full document.
+    let html = match (
+        html.find("<!--StartFragment-->"),
+        html.find("<!--EndFragment-->"),
+    ) {
+        (Some(start), Some(end)) if start < end => &html[start + 20..end],
+        _ => html,
+    };
+
+    let mut text = String::new();
+    let mut skip_depth = 0usize;
+    let mut pre_depth = 0usize;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        // A `<` that is never closed is not a tag, e.g. "a < b" in unescaped text.
+        let tag_end = rest.starts_with('<').then(|| rest.find('>')).flatten();
+        if let Some(end) = tag_end {
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+
+            let closing = tag.starts_with('/');
+            let name = tag
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+
+            if SKIPPED_HTML_TAGS.contains(&name.as_str()) {
+                if closing {
+                    skip_depth = skip_depth.saturating_sub(1);
+                } else {
+                    skip_depth += 1;
+                }
+                continue;
+            }
+            if skip_depth > 0 {
+                continue;
+            }
+
+            match name.as_str() {
+                "br" => text.push('\n'),
+                "td" | "th" if closing => text.push('\t'),
+                "pre" if closing => pre_depth = pre_depth.saturating_sub(1),
+                "pre" => pre_depth += 1,
+                _ => {}
+            }
+            if BLOCK_TAGS.contains(&name.as_str()) && !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            continue;
+        }
+
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c == '<')
+            .map_or(rest.len(), |(end, _)| end);
+        let segment = &rest[..end];
+        rest = &rest[end..];
+
+        if skip_depth > 0 {
+            continue;
+        }
+
+        let decoded = decode_entities(segment);
+        if pre_depth > 0 {
+            text.push_str(&decoded);
+        } else {
+            for c in decoded.chars() {
+                if c.is_whitespace() && c != '\u{a0}' {
+                    if !text.is_empty() && !text.ends_with([' ', '\n', '\t']) {
+                        text.push(' ');
+                    }
+                } else if c == '\u{a0}' {
+                    text.push(' ');
+                } else {
+                    text.push(c);
+                }
+            }
+        }
+    }
+
+    tidy_lines(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end + 1]).map(|c| (c, end + 2)));
+
+        match entity {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    if let Some(code) = name.strip_prefix('#') {
+        let value = match code.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => code.parse().ok()?,
+        };
+        return char::from_u32(value);
+    }
+
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "bull" => '•',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        _ => return None,
+    })
+}
+
+/// RTF destinations whose content is not part of the document text.
+const SKIPPED_RTF_DESTINATIONS: &[&str] = &[
+    "colortbl",
+    "datastore",
+    "fonttbl",
+    "footer",
+    "generator",
+    "header",
+    "info",
+    "latentstyles",
+    "listoverridetable",
+    "listtable",
+    "object",
+    "pict",
+    "rsidtbl",
+    "stylesheet",
+    "themedata",
+    "xmlnstbl",
+];
+
+struct RtfGroup {
+    skip: bool,
+    unicode_skip: usize,
+}
+
+pub fn rtf_to_text(rtf: &str) -> String {
+    let mut text = String::new();
+    let mut groups = vec![RtfGroup {
+        skip: false,
+        unicode_skip: 1,
+    }];
+    let mut pending_skip = 0usize;
+    let mut high_surrogate: Option<u32> = None;
+    let mut chars = rtf.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let group = groups.last_mut().unwrap();
+
+        match c {
+            '{' => {
+                let inherited = RtfGroup {
+                    skip: group.skip,
+                    unicode_skip: group.unicode_skip,
+                };
+                groups.push(inherited);
+            }
+            '}' => {
+                if groups.len() > 1 {
+                    groups.pop();
+                }
+            }
+            '\r' | '\n' => {}
+            '\\' => {
+                let Some(&next) = chars.peek() else {
+                    break;
+                };
+
+                if !next.is_ascii_alphabetic() {
+                    chars.next();
+                    let symbol = match next {
+                        '\\' | '{' | '}' => Some(next),
+                        '~' => Some(' '),
+                        '_' => Some('-'),
+                        '\'' => {
+                            let hex: String = chars.by_ref().take(2).collect();
+                            u8::from_str_radix(&hex, 16).ok().map(cp1252_to_char)
+                        }
+                        '*' => {
+                            group.skip = true;
+                            None
+                        }
+                        '\n' | '\r' => Some('\n'),
+                        _ => None,
+                    };
+
+                    if let Some(symbol) = symbol {
+                        if pending_skip > 0 {
+                            pending_skip -= 1;
+                        } else if !group.skip {
+                            text.push(symbol);
+                        }
+                    }
+                    continue;
+                }
+
+                let mut word = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                    word.push(c);
+                    chars.next();
+                }
+
+                let mut param = String::new();
+                if chars.peek() == Some(&'-') {
+                    param.push('-');
+                    chars.next();
+                }
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                    param.push(c);
+                    chars.next();
+                }
+                chars.next_if_eq(&' ');
+                let param: Option<i32> = param.parse().ok();
+
+                if SKIPPED_RTF_DESTINATIONS.contains(&word.as_str()) {
+                    group.skip = true;
+                    continue;
+                }
+
+                let output = match word.as_str() {
+                    "par" | "line" | "row" => Some('\n'),
+                    "tab" | "cell" => Some('\t'),
+                    "emdash" => Some('—'),
+                    "endash" => Some('–'),
+                    "bullet" => Some('•'),
+                    "lquote" => Some('‘'),
+                    "rquote" => Some('’'),
+                    "ldblquote" => Some('“'),
+                    "rdblquote" => Some('”'),
+                    "uc" => {
+                        group.unicode_skip = param.unwrap_or(1).max(0) as usize;
+                        None
+                    }
+                    "u" => {
+                        // Negative values encode code points above 32767 as signed
+                        // 16-bit, and characters outside the BMP come as a UTF-16
+                        // surrogate pair of two `\u` words.
+                        let unit = param
+                            .map(|value| (if value < 0 { value + 65536 } else { value }) as u32);
+                        let is_low = matches!(unit, Some(0xdc00..=0xdfff));
+                        if !is_low && high_surrogate.take().is_some() && !group.skip {
+                            text.push('\u{fffd}');
+                        }
+                        let decoded = match (unit, high_surrogate.take()) {
+                            (Some(low @ 0xdc00..=0xdfff), Some(high)) => {
+                                char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))
+                            }
+                            (Some(high @ 0xd800..=0xdbff), _) => {
+                                high_surrogate = Some(high);
+                                None
+                            }
+                            (Some(unit), _) => Some(char::from_u32(unit).unwrap_or('\u{fffd}')),
+                            (None, _) => None,
+                        };
+                        if let Some(decoded) = decoded.filter(|_| !group.skip) {
+                            text.push(decoded);
+                        }
+                        pending_skip = group.unicode_skip;
+                        None
+                    }
+                    _ => None,
+                };
+
+                if let Some(output) = output {
+                    if !group.skip {
+                        text.push(output);
+                    }
+                }
+            }
+            _ => {
+                if pending_skip > 0 {
+                    pending_skip -= 1;
+                } else if !group.skip {
+                    if high_surrogate.take().is_some() {
+                        text.push('\u{fffd}');
+                    }
+                    text.push(c);
+                }
+            }
+        }
+    }
+    if high_surrogate.is_some() {
+        text.push('\u{fffd}');
+    }
+
+    tidy_lines(&text)
+}
+
+fn cp1252_to_char(byte: u8) -> char {
+    const HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž',
+        '\u{8f}', '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}',
+        'ž', 'Ÿ',
+    ];
+
+    match byte {
+        0x80..=0x9f => HIGH[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+/// Trims trailing spaces and collapses runs of blank lines left behind by markup.
+fn tidy_lines(text: &str) -> String {
+    let mut tidied = String::with_capacity(text.len());
+    let mut blank_lines = 0;
+
+    for line in text.trim_matches('\n').lines() {
+        let line = line.trim_end_matches([' ', '\t']);
+        if line.trim().is_empty() {
+            blank_lines += 1;
+            if blank_lines > 1 {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+
+        if !tidied.is_empty() {
+            tidied.push('\n');
+        }
+        tidied.push_str(line);
+    }
+
+    tidied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn html_decodes_entities() {
+        assert_eq!(
+            html_to_text("Tom &amp; Jerry &lt;3 &#169; &#x263A; &hellip;"),
+            "Tom & Jerry <3 © ☺ …"
+        );
+        assert_eq!(html_to_text("a&nbsp;&nbsp;b"), "a  b");
+    }
+
+    #[test]
+    fn html_leaves_unknown_entities_alone() {
+        assert_eq!(html_to_text("&foo; & &amp"), "&foo; & &amp");
+        assert_eq!(html_to_text("&#xZZ; &#99999999;"), "&#xZZ; &#99999999;");
+    }
+
+    #[test]
+    fn html_breaks_lines_at_br_and_block_tags() {
+        assert_eq!(
+            html_to_text("<p>One</p><p>Two<br>Three</p><ul><li>A</li><li>B</li></ul>"),
+            "One\nTwo\nThree\nA\nB"
+        );
+        assert_eq!(html_to_text("<h1>Title</h1>Body<br/>"), "Title\nBody");
+    }
+
+    #[test]
+    fn html_separates_table_cells_with_tabs() {
+        assert_eq!(
+            html_to_text(
+                "<table><tr><td>a</td><td>b</td></tr><tr><th>c</th><th>d</th></tr></table>"
+            ),
+            "a\tb\nc\td"
+        );
+    }
+
+    #[test]
+    fn html_collapses_whitespace_outside_pre() {
+        assert_eq!(
+            html_to_text("<div>  lots   of\n space </div><pre>  keep\n  this</pre>"),
+            "lots of space\n  keep\n  this"
+        );
+    }
+
+    #[test]
+    fn html_skips_comments_scripts_and_styles() {
+        assert_eq!(
+            html_to_text(
+                "<head><title>T</title></head><style>p { color: red }</style>\
+                 <script>alert(1)</script>Hi<!-- note -->!"
+            ),
+            "Hi!"
+        );
+    }
+
+    #[test]
+    fn html_keeps_only_the_copied_fragment() {
+        let html = "Version:0.9\r\nStartHTML:0000000105\r\n<html><body>Before\
+                    <!--StartFragment--><b>bold</b> text<!--EndFragment-->After</body></html>";
+        assert_eq!(html_to_text(html), "bold text");
+
+        // Markers out of order are ignored rather than sliced.
+        assert_eq!(
+            html_to_text("a<!--EndFragment-->b<!--StartFragment-->c"),
+            "abc"
+        );
+    }
+
+    #[test]
+    fn html_keeps_a_lone_less_than_sign() {
+        assert_eq!(html_to_text("a < b &amp; c"), "a < b & c");
+        assert_eq!(html_to_text("<b>1</b> <2 and é<"), "1 <2 and é<");
+    }
+
+    #[test]
+    fn rtf_keeps_text_and_skips_tables() {
+        assert_eq!(
+            rtf_to_text(
+                r"{\rtf1\ansi{\fonttbl{\f0 Arial;}}{\colortbl;\red0\green0\blue0;}\f0 Hello\par World\tab!}"
+            ),
+            "Hello\nWorld\t!"
+        );
+        assert_eq!(
+            rtf_to_text(r"{\rtf1 {\*\generator Riched20 10.0;}Text}"),
+            "Text"
+        );
+    }
+
+    #[test]
+    fn rtf_decodes_escapes_and_hex_bytes() {
+        assert_eq!(rtf_to_text(r"{\rtf1 a\{b\}c\\d\~e}"), "a{b}c\\d e");
+        assert_eq!(
+            rtf_to_text(r"{\rtf1 caf\'e9 \'93quoted\'94}"),
+            "café “quoted”"
+        );
+    }
+
+    #[test]
+    fn rtf_skips_unicode_fallbacks_per_uc() {
+        assert_eq!(
+            rtf_to_text(r"{\rtf1 \u8364?\uc2\u8364xx done\uc0\u8364!}"),
+            "€€ done€!"
+        );
+        assert_eq!(rtf_to_text(r"{\rtf1 \u-3913?}"), "\u{f0b7}");
+    }
+
+    #[test]
+    fn rtf_scopes_uc_to_groups() {
+        assert_eq!(rtf_to_text(r"{\rtf1 {\uc0 \u233}\u233?}"), "éé");
+    }
+
+    #[test]
+    fn rtf_joins_surrogate_pairs() {
+        assert_eq!(rtf_to_text(r"{\rtf1 \u-10179?\u-8704?}"), "😀");
+        assert_eq!(rtf_to_text(r"{\rtf1 \uc0\u55357\u56832  ok}"), "😀 ok");
+    }
+
+    #[test]
+    fn rtf_replaces_unpaired_surrogates() {
+        assert_eq!(rtf_to_text(r"{\rtf1 \u-8704?x}"), "\u{fffd}x");
+        assert_eq!(rtf_to_text(r"{\rtf1 \u-10179?\u65?}"), "\u{fffd}A");
+        assert_eq!(rtf_to_text(r"{\rtf1 a\u-10179?b}"), "a\u{fffd}b");
+        assert_eq!(rtf_to_text(r"{\rtf1 a\u-10179?}"), "a\u{fffd}");
+    }
+}
@@ -0,0 +1,484 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clipboard_rs::common::RustImage;
+use clipboard_rs::{
+    Clipboard, ClipboardContext, ClipboardHandler, ClipboardWatcher, ClipboardWatcherContext,
+    ContentFormat, WatcherShutdown,
+};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::capture::{set_capture_error, CaptureState};
+use crate::clipboard_metadata::{get_clipboard_source_url, get_foreground_window_title};
+use crate::clipboard_snapshot::ClipboardSnapshot;
+use crate::config::ConfigState;
+use crate::history::{History, NewEntry};
+
+/// Records clipboard changes into history, in the app or in the daemon.
+pub struct Recorder {
+    /// Set while the clipboard is being watched.
+    watcher: Mutex<Option<WatcherShutdown>>,
+    /// The content and type last recorded, so repeated change notifications for
+    /// the same copy are only counted once.
+    last_recorded: Mutex<Option<(String, String)>>,
+}
+
+pub fn init_recorder() -> Recorder {
+    Recorder {
+        watcher: Mutex::new(None),
+        last_recorded: Mutex::new(None),
+    }
+}
+
+struct RecordOnChange(AppHandle);
+
+impl ClipboardHandler for RecordOnChange {
+    fn on_clipboard_change(&mut self) {
+        // Runs on the watcher's thread, so changes are recorded one at a time.
+        tauri::async_runtime::block_on(record_clipboard(&self.0));
+    }
+}
+
+/// Starts watching the clipboard on its own thread, unless that is already
+/// happening, and records what is on it now.
+pub fn start_recording(app_handle: &AppHandle) {
+    let recorder = app_handle.state::<Recorder>();
+    let mut watcher = recorder.watcher.lock().unwrap();
+    if watcher.is_some() {
+        return;
+    }
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let handler = RecordOnChange(app_handle.clone());
+    std::thread::spawn(move || {
+        let mut context = match ClipboardWatcherContext::new() {
+            Ok(context) => context,
+            Err(e) => {
+                let _ = sender.send(Err(e.to_string()));
+                return;
+            }
+        };
+        let _ = sender.send(Ok(context.add_handler(handler).get_shutdown_channel()));
+        context.start_watch();
+    });
+
+    match receiver
+        .recv()
+        .map_err(|e| e.to_string())
+        .and_then(|started| started)
+    {
+        Ok(shutdown) => *watcher = Some(shutdown),
+        Err(e) => {
+            set_capture_error(
+                app_handle,
+                Some(format!("Failed to watch the clipboard: {}", e)),
+            );
+            return;
+        }
+    }
+    drop(watcher);
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = app_handle.state::<History>().ensure_schema().await {
+            set_capture_error(&app_handle, Some(e));
+            return;
+        }
+        record_clipboard(&app_handle).await;
+    });
+}
+
+/// Stops watching the clipboard, so that a daemon can record instead.
+pub fn stop_recording(app_handle: &AppHandle) {
+    if let Some(shutdown) = app_handle
+        .state::<Recorder>()
+        .watcher
+        .lock()
+        .unwrap()
+        .take()
+    {
+        shutdown.stop();
+    }
+}
+
+/// Whether clipboard changes should be left out of history right now: during
+/// Nabu's own pastes, while capture is paused, or when the focused app is ignored
+/// in the config file.
+fn is_capture_suppressed(app_handle: &AppHandle) -> bool {
+    app_handle
+        .state::<ClipboardSnapshot>()
+        .is_suppressing_capture()
+        || app_handle.state::<CaptureState>().is_paused()
+        || crate::foreground::foreground_window()
+            .is_some_and(|window| app_handle.state::<ConfigState>().get().ignores(&window))
+}
+
+async fn record_clipboard(app_handle: &AppHandle) {
+    if is_capture_suppressed(app_handle) {
+        return;
+    }
+
+    let entry = match read_clipboard() {
+        Ok(Some(entry)) => entry,
+        Ok(None) => return,
+        Err(e) => {
+            set_capture_error(app_handle, Some(e));
+            return;
+        }
+    };
+
+    {
+        let recorder = app_handle.state::<Recorder>();
+        let mut last_recorded = recorder.last_recorded.lock().unwrap();
+        let recorded = (entry.content.clone(), entry.content_type.clone());
+        if last_recorded.as_ref() == Some(&recorded) {
+            return;
+        }
+        *last_recorded = Some(recorded);
+    }
+
+    match app_handle.state::<History>().record(&entry).await {
+        Ok(_) => {
+            set_capture_error(app_handle, None);
+            notify_entry_recorded(app_handle);
+            crate::config::prune_history(app_handle);
+        }
+        Err(e) => set_capture_error(app_handle, Some(e)),
+    }
+}
+
+/// Tells the tray and the panel that history has a new entry.
+pub fn notify_entry_recorded(app_handle: &AppHandle) {
+    crate::tray::flash_new_entry(app_handle);
+    crate::tray::refresh_menu(app_handle);
+    let _ = app_handle.emit("entry-recorded", ());
+}
+
+/// Reads the clipboard into an entry the way the panel used to: the richest
+/// format wins, and text that is a link, email address or colour is typed as such.
+fn read_clipboard() -> Result<Option<NewEntry>, String> {
+    let clipboard =
+        ClipboardContext::new().map_err(|e| format!("Failed to open clipboard: {}", e))?;
+    let text = || clipboard.get_text().unwrap_or_default();
+    let mut source_url = String::new();
+
+    let (content, content_type, preview, plain_text, details) =
+        if clipboard.has(ContentFormat::Image) {
+            let image = clipboard
+                .get_image()
+                .map_err(|e| format!("Failed to read image: {}", e))?;
+            let (width, height) = image.get_size();
+            let png = image
+                .to_png()
+                .map_err(|e| format!("Failed to encode image: {}", e))?;
+            let bytes = png.get_bytes();
+            (
+                STANDARD.encode(bytes),
+                "image",
+                format!("Image [{}x{}]", width, height),
+                String::new(),
+                Some(format!("[{} KB]", (bytes.len() as f64 / 1024.0).round())),
+            )
+        } else if clipboard.has(ContentFormat::Html) {
+            let plain_text = text();
+            source_url = get_clipboard_source_url().unwrap_or_default();
+            (
+                clipboard.get_html().unwrap_or_default(),
+                "html",
+                plain_text.clone(),
+                plain_text,
+                None,
+            )
+        } else if clipboard.has(ContentFormat::Rtf) {
+            let plain_text = text();
+            (
+                clipboard.get_rich_text().unwrap_or_default(),
+                "rtf",
+                plain_text.clone(),
+                plain_text,
+                None,
+            )
+        } else if clipboard.has(ContentFormat::Files) {
+            let files = clipboard.get_files().unwrap_or_default().join("\n");
+            (files.clone(), "file", files, String::new(), None)
+        } else if clipboard.has(ContentFormat::Text) {
+            let plain_text = text();
+            (
+                plain_text.clone(),
+                "text",
+                plain_text.clone(),
+                plain_text,
+                None,
+            )
+        } else {
+            return Ok(None);
+        };
+
+    if content.is_empty() {
+        return Ok(None);
+    }
+
+    let content_type = special_type(&plain_text).unwrap_or(content_type);
+
+    let (title, source) = split_window_title(&get_foreground_window_title().unwrap_or_default());
+    let metadata = match details {
+        Some(details) => format!("[{}] ({}) {}", title, source, details),
+        None => format!("[{}] ({})", title, source),
+    };
+
+    Ok(Some(NewEntry {
+        content,
+        content_type: content_type.to_string(),
+        preview,
+        metadata,
+        source_url,
+    }))
+}
+
+/// The type the panel shows `text` as, if it is a single link, email address
+/// or CSS colour. Ported from the regexes in `clipboard-parser.ts`, which are
+/// kept in the comments below.
+fn special_type(text: &str) -> Option<&'static str> {
+    let text = text.trim_matches(is_space);
+    if is_link(text) {
+        Some("link")
+    } else if is_email(text) {
+        Some("email")
+    } else if is_color(text) {
+        Some("color")
+    } else {
+        None
+    }
+}
+
+/// Whether `c` matches `\s` in a JavaScript regex.
+fn is_space(c: char) -> bool {
+    c == '\u{feff}' || (c.is_whitespace() && c != '\u{85}')
+}
+
+/// Whether `c` ends a line, which `.` in a JavaScript regex doesn't match.
+fn is_line_break(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+/// `/^(https?:\/\/[^\s/$.?#].[^\s]*)$/i`
+fn is_link(text: &str) -> bool {
+    let lower = text.to_ascii_lowercase();
+    let Some(rest) = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"))
+    else {
+        return false;
+    };
+    let mut chars = rest.chars();
+    matches!(chars.next(), Some(c) if !is_space(c) && !"/$.?#".contains(c))
+        && matches!(chars.next(), Some(c) if !is_line_break(c))
+        && !chars.any(is_space)
+}
+
+/// `/^(([^<>()[\]\\.,;:\s@"]+(\.[^<>()[\]\\.,;:\s@"]+)*)|(".+"))@((\[[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}])|(([a-zA-Z\-0-9]+\.)+[a-zA-Z]{2,}))$/`
+fn is_email(text: &str) -> bool {
+    // Neither form of the domain can contain `@`, so the last one splits it off.
+    let Some((local, domain)) = text.rsplit_once('@') else {
+        return false;
+    };
+    is_email_local_part(local) && is_email_domain(domain)
+}
+
+fn is_email_local_part(local: &str) -> bool {
+    let quoted = local
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .is_some_and(|inner| !inner.is_empty() && !inner.chars().any(is_line_break));
+
+    quoted
+        || local.split('.').all(|atom| {
+            !atom.is_empty()
+                && !atom
+                    .chars()
+                    .any(|c| is_space(c) || "<>()[]\\.,;:@\"".contains(c))
+        })
+}
+
+fn is_email_domain(domain: &str) -> bool {
+    if let Some(address) = domain
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+    {
+        let octets: Vec<&str> = address.split('.').collect();
+        return octets.len() == 4
+            && octets.iter().all(|octet| {
+                (1..=3).contains(&octet.len()) && octet.chars().all(|c| c.is_ascii_digit())
+            });
+    }
+
+    let Some((labels, tld)) = domain.rsplit_once('.') else {
+        return false;
+    };
+    tld.len() >= 2
+        && tld.chars().all(|c| c.is_ascii_alphabetic())
+        && labels.split('.').all(|label| {
+            !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// `/^(#([0-9a-f]{3}){1,2}|(rgb|hsl)a?\((-?\d+%?[,\s]+){2,3}\s*[\d.]+%?\))$/i`
+fn is_color(text: &str) -> bool {
+    let lower = text.to_ascii_lowercase();
+    if let Some(hex) = lower.strip_prefix('#') {
+        return matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit());
+    }
+
+    let Some(arguments) = ["rgba(", "rgb(", "hsla(", "hsl("]
+        .iter()
+        .find_map(|function| lower.strip_prefix(function))
+        .and_then(|rest| rest.strip_suffix(')'))
+    else {
+        return false;
+    };
+
+    // `(-?\d+%?[,\s]+){2,3}`: whole numbers, each followed by separators. Taking
+    // as many as possible never stops the rest from matching, since the last
+    // value can't be followed by a separator.
+    let mut rest = arguments;
+    let mut values = 0;
+    while values < 3 {
+        let value = rest.strip_prefix('-').unwrap_or(rest);
+        let Some(after) = strip_digits(value) else {
+            break;
+        };
+        let after = after.strip_prefix('%').unwrap_or(after);
+        let separated = after.trim_start_matches(|c: char| c == ',' || is_space(c));
+        if separated.len() == after.len() {
+            break;
+        }
+        rest = separated;
+        values += 1;
+    }
+
+    // `\s*[\d.]+%?`: the last value, which may have a decimal point.
+    let last = rest.trim_start_matches(is_space);
+    let last = last.strip_suffix('%').unwrap_or(last);
+    values >= 2 && !last.is_empty() && last.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+/// `text` without its leading digits, if it has any.
+fn strip_digits(text: &str) -> Option<&str> {
+    let rest = text.trim_start_matches(|c: char| c.is_ascii_digit());
+    (rest.len() < text.len()).then_some(rest)
+}
+
+/// Splits a title like `Page - Browser` into the page and the app, as
+/// `parseWindowTitle` in the panel used to.
+fn split_window_title(raw: &str) -> (String, String) {
+    // Split from the left like `String.split`, which matters for "a - - b".
+    let Some((separator, _)) = raw.match_indices(" - ").last() else {
+        return (raw.to_string(), String::new());
+    };
+    let (title, source) = (&raw[..separator], &raw[separator + " - ".len()..]);
+
+    let mut title = title.to_string();
+    // `/\s*and \d+ more pages\s*/i`: browsers append this when several tabs are
+    // selected.
+    let lower = title.to_ascii_lowercase();
+    let more_pages = lower.match_indices("and ").find_map(|(start, _)| {
+        let rest = &lower[start + "and ".len()..];
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        (digits > 0 && rest[digits..].starts_with(" more pages"))
+            .then(|| start..start + "and ".len() + digits + " more pages".len())
+    });
+    if let Some(range) = more_pages {
+        remove_with_spaces(&mut title, range);
+    }
+    // `/\s*Personal\s*/i`
+    if let Some(start) = title.to_ascii_lowercase().find("personal") {
+        remove_with_spaces(&mut title, start..start + "personal".len());
+    }
+    // `/\s*-\s*$/`
+    let title = title.trim_end_matches(is_space);
+    let title = title.strip_suffix('-').unwrap_or(title);
+
+    (
+        title.trim_matches(is_space).to_string(),
+        source.trim_matches(is_space).to_string(),
+    )
+}
+
+/// Removes `range` from `text` along with the whitespace on either side.
+fn remove_with_spaces(text: &mut String, range: std::ops::Range<usize>) {
+    let start = text[..range.start].trim_end_matches(is_space).len();
+    let after = &text[range.end..];
+    let end = range.end + after.len() - after.trim_start_matches(is_space).len();
+    text.replace_range(start..end, "");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each case with the type `detectSpecialType` in `clipboard-parser.ts` gave it.
+    const SPECIAL_TYPES: [(&str, Option<&str>); 29] = [
+        ("https://example.com/path?q=1", Some("link")),
+        ("HTTP://EXAMPLE.COM", Some("link")),
+        ("http://a b", Some("link")),
+        ("http://x", None),
+        ("https://.example.com", None),
+        ("ftp://example.com", None),
+        (" https://example.com \n", Some("link")),
+        ("user@example.com", Some("email")),
+        ("first.last@sub.example.co", Some("email")),
+        ("\"john doe\"@example.com", Some("email")),
+        ("a@[192.168.0.1]", Some("email")),
+        ("a..b@example.com", None),
+        (".a@example.com", None),
+        ("a@example.c", None),
+        ("a@example.c0m", None),
+        ("a b@example.com", None),
+        ("#fff", Some("color")),
+        ("#A0b1C2", Some("color")),
+        ("#ffff", None),
+        ("rgb(255, 0, 0)", Some("color")),
+        ("RGBA(255,0,0,0.5)", Some("color")),
+        ("hsl(120 50% 50%)", Some("color")),
+        ("hsla(-120, 50%, 50%, .5)", Some("color")),
+        ("rgb(nan, inf, 1e5)", None),
+        ("rgb(1.5, 2, 3)", None),
+        ("rgb(1, 2)", None),
+        ("rgb(1, 2, 3, 4, 5)", None),
+        ("rgb(1, 2, -3)", None),
+        ("plain text", None),
+    ];
+
+    /// Each title with what `parseWindowTitle` split it into.
+    const WINDOW_TITLES: [(&str, (&str, &str)); 8] = [
+        ("Page - Google Chrome", ("Page", "Google Chrome")),
+        (
+            "Docs and 3 more pages - Personal - Microsoft Edge",
+            ("Docs", "Microsoft Edge"),
+        ),
+        ("Inbox - Personal - Outlook", ("Inbox", "Outlook")),
+        ("a - - b", ("a", "- b")),
+        ("Notes -  - Editor", ("Notes", "Editor")),
+        ("Untitled", ("Untitled", "")),
+        ("Band 3 more pages - App", ("B", "App")),
+        ("Report - Q3 - Excel", ("Report - Q3", "Excel")),
+    ];
+
+    #[test]
+    fn special_types_match_the_panel() {
+        for (text, expected) in SPECIAL_TYPES {
+            assert_eq!(special_type(text), expected, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn window_titles_split_like_the_panel() {
+        for (raw, (title, source)) in WINDOW_TITLES {
+            assert_eq!(
+                split_window_title(raw),
+                (title.to_string(), source.to_string()),
+                "{:?}",
+                raw
+            );
+        }
+    }
+}
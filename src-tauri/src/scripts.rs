@@ -0,0 +1,223 @@
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Manager};
+
+use crate::entry::ClipboardEntry;
+use crate::paste::{write_and_paste, write_plain_text, PasteError};
+
+/// User scripts live in `<config dir>/transforms/*.rhai`.
+const SCRIPTS_DIR: &str = "transforms";
+const SCRIPT_EXTENSION: &str = "rhai";
+
+/// Scripts still running after this long are stopped.
+const SCRIPT_TIME_LIMIT: Duration = Duration::from_secs(2);
+
+/// A user-provided transform script, as listed in the entry context menu.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptTransform {
+    /// The file name without extension, used to run the script.
+    pub name: String,
+    /// Taken from a leading `//` comment, if the script has one.
+    pub description: Option<String>,
+}
+
+fn scripts_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| e.to_string())?
+        .join(SCRIPTS_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    Ok(dir)
+}
+
+/// Whether `name` can only refer to a file directly in the scripts directory:
+/// no separators, no `..`, no hidden files and no Windows drive prefix.
+fn is_script_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.contains(['/', '\\', ':'])
+        && !name.contains("..")
+        && !name.starts_with('.')
+}
+
+fn script_path(app: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    if !is_script_name(name) {
+        return Err(format!("Invalid script name: {}", name));
+    }
+    Ok(scripts_dir(app)?.join(format!("{}.{}", name, SCRIPT_EXTENSION)))
+}
+
+/// An engine without module imports and with limits on runtime and memory.
+/// Rhai itself has no filesystem or network access.
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.set_max_call_levels(64);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(10 * 1024 * 1024);
+    engine.set_max_array_size(100_000);
+    engine.set_max_map_size(100_000);
+    engine.on_print(|_| {});
+    engine.on_debug(|_, _, _| {});
+
+    let started = Instant::now();
+    engine.on_progress(move |_| (started.elapsed() > SCRIPT_TIME_LIMIT).then_some(Dynamic::UNIT));
+    engine
+}
+
+/// Runs `script` with the entry's plain text bound to `text` and its metadata to
+/// `entry`. The script's result must be a string.
+pub fn run_script(script: &str, entry: &ClipboardEntry) -> Result<String, String> {
+    let mut metadata = Map::new();
+    metadata.insert("id".into(), Dynamic::from(entry.id));
+    metadata.insert("content_type".into(), entry.content_type.clone().into());
+    metadata.insert("content".into(), entry.content.clone().into());
+
+    let mut scope = Scope::new();
+    scope.push_constant("text", entry.plain_text()?);
+    scope.push_constant("entry", metadata);
+
+    sandboxed_engine()
+        .eval_with_scope::<String>(&mut scope, script)
+        .map_err(|e| match *e {
+            EvalAltResult::ErrorTerminated(..) => format!(
+                "Script did not finish within {} s",
+                SCRIPT_TIME_LIMIT.as_secs()
+            ),
+            EvalAltResult::ErrorMismatchOutputType(..) => "Script must return a string".to_string(),
+            e => format!("Script failed: {}", e),
+        })
+}
+
+async fn run_named_script(
+    app: &AppHandle,
+    name: &str,
+    entry: ClipboardEntry,
+) -> Result<String, String> {
+    let path = script_path(app, name)?;
+    let script = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read script {}: {}", name, e))?;
+
+    tauri::async_runtime::spawn_blocking(move || run_script(&script, &entry))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[command]
+pub fn list_script_transforms(app: AppHandle) -> Result<Vec<ScriptTransform>, String> {
+    let dir = scripts_dir(&app)?;
+    let mut scripts: Vec<ScriptTransform> = std::fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read {:?}: {}", dir, e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == SCRIPT_EXTENSION))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            let description = std::fs::read_to_string(&path).ok().and_then(|script| {
+                script
+                    .lines()
+                    .next()
+                    .and_then(|line| line.strip_prefix("//"))
+                    .map(|comment| comment.trim().to_string())
+                    .filter(|comment| !comment.is_empty())
+            });
+            Some(ScriptTransform { name, description })
+        })
+        .collect();
+
+    scripts.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(scripts)
+}
+
+/// Returns the output of the script `name` for `entry`, e.g. to save as a new entry.
+#[command]
+pub async fn run_script_transform(
+    app: AppHandle,
+    name: String,
+    entry: ClipboardEntry,
+) -> Result<String, String> {
+    run_named_script(&app, &name, entry).await
+}
+
+/// Pastes the output of the script `name` for `entry` as plain text.
+#[command]
+pub async fn paste_script_transform(
+    app: AppHandle,
+    name: String,
+    entry: ClipboardEntry,
+    restore_clipboard: Option<bool>,
+) -> Result<(), PasteError> {
+    let restore_clipboard = restore_clipboard.unwrap_or(false);
+    let text = run_named_script(&app, &name, entry).await?;
+
+    write_and_paste(&app, restore_clipboard, || write_plain_text(text)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(content_type: &str, content: &str) -> ClipboardEntry {
+        ClipboardEntry {
+            id: 7,
+            content: content.into(),
+            content_type: content_type.into(),
+            preview: None,
+        }
+    }
+
+    #[test]
+    fn binds_text_and_entry() {
+        assert_eq!(
+            run_script(
+                r#"`${text.to_upper()} ${entry.id} ${entry.content_type} ${entry.content}`"#,
+                &entry("html", "<b>hi</b>")
+            ),
+            Ok("HI 7 html <b>hi</b>".to_string())
+        );
+    }
+
+    #[test]
+    fn bindings_are_constant() {
+        assert!(run_script(r#"text = "x"; text"#, &entry("text", "hi")).is_err());
+    }
+
+    #[test]
+    fn output_must_be_a_string() {
+        assert_eq!(
+            run_script("text.len()", &entry("text", "hi")),
+            Err("Script must return a string".to_string())
+        );
+    }
+
+    #[test]
+    fn imports_are_rejected() {
+        let result = run_script(r#"import "helpers" as h; text"#, &entry("text", "hi"));
+        assert!(result.is_err_and(|e| e.starts_with("Script failed")));
+    }
+
+    #[test]
+    fn stops_scripts_after_the_time_limit() {
+        let started = Instant::now();
+        assert_eq!(
+            run_script("loop {}", &entry("text", "hi")),
+            Err(format!(
+                "Script did not finish within {} s",
+                SCRIPT_TIME_LIMIT.as_secs()
+            ))
+        );
+        assert!(started.elapsed() < SCRIPT_TIME_LIMIT * 2);
+    }
+
+    #[test]
+    fn script_names_stay_in_the_scripts_directory() {
+        assert!(is_script_name("title-case"));
+        assert!(is_script_name("v1.2"));
+        for name in ["", ".hidden", "a/b", "a\\b", "a..b", "..", "C:evil", "c:"] {
+            assert!(!is_script_name(name), "{:?}", name);
+        }
+    }
+}
@@ -0,0 +1,439 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Emitter, Listener, Manager, State};
+
+use crate::external_command::split_command_line;
+use crate::history::History;
+use crate::macros::PasteMacro;
+use crate::paste::PasteRule;
+use crate::placement::PanelPlacement;
+use crate::shortcuts::{parse_accelerator, ShortcutAction};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+const MAX_TYPING_DELAY_MS: u64 = 1000;
+
+/// Bumped whenever a change to [`Settings`] needs existing files migrated.
+const SETTINGS_VERSION: u32 = 2;
+
+/// Settings the panel used to keep in its `settings` table, with the fields
+/// they now live in and the settings version that moved them. Each is copied
+/// over once, by the first version that has it.
+const LEGACY_KEYS: [(&str, &str, u32); 14] = [
+    ("auto_start", "autoStart", 1),
+    ("hide_on_blur", "hideOnBlur", 1),
+    ("panel_placement", "panelPlacement", 1),
+    ("restore_clipboard", "restoreClipboard", 1),
+    ("join_separator", "joinSeparator", 1),
+    ("external_commands", "externalCommands", 1),
+    ("external_editor", "externalEditor", 1),
+    ("toggle_shortcut", "toggleShortcut", 2),
+    ("keyboard_navigation", "keyboardNavigation", 2),
+    ("paste_macros", "pasteMacros", 2),
+    ("left_panel", "leftPanelSize", 2),
+    ("right_panel", "rightPanelSize", 2),
+    ("right_panel_collapsed", "rightPanelCollapsed", 2),
+    ("filter_sidebar_open", "filterSidebarCollapsed", 2),
+];
+
+/// A command that entries can be piped through, as listed in the entry context menu.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalCommand {
+    pub name: String,
+    pub command_line: String,
+}
+
+/// A key with modifiers, as recorded by the panel's shortcut recorder: modifiers
+/// like `ctrl` and a `KeyboardEvent.code` like `KeyV`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBinding {
+    pub modifiers: Vec<String>,
+    pub key: String,
+}
+
+/// A global shortcut for an action other than the panel toggle, written as in
+/// `nabu.toml`, so it can be a chord like `ctrl+KeyK KeyV`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlobalShortcut {
+    pub action: ShortcutAction,
+    pub accelerator: String,
+}
+
+/// The panel's in-window shortcuts. Only the ones the user changed are stored;
+/// the panel fills in the rest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct KeyboardNavigation {
+    pub shortcuts: BTreeMap<String, KeyBinding>,
+    pub page_size: u32,
+    pub prefetch_threshold: u32,
+}
+
+impl Default for KeyboardNavigation {
+    fn default() -> Self {
+        KeyboardNavigation {
+            shortcuts: BTreeMap::new(),
+            page_size: 10,
+            prefetch_threshold: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeMode {
+    #[default]
+    Dark,
+    Light,
+    System,
+}
+
+/// Settings shared by the backend and every window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct Settings {
+    pub version: u32,
+    pub auto_start: bool,
+    pub hide_on_blur: bool,
+    pub panel_placement: PanelPlacement,
+    pub restore_clipboard: bool,
+    pub join_separator: String,
+    /// Pause between characters when typing an entry out, for apps that drop
+    /// fast input.
+    pub typing_delay_ms: u64,
+    /// Paste strategies per app, checked after the config file's.
+    pub paste_rules: Vec<PasteRule>,
+    pub external_commands: Vec<ExternalCommand>,
+    /// Empty for the system's default application.
+    pub external_editor: String,
+    /// The global shortcut that shows and hides the panel.
+    pub toggle_shortcut: KeyBinding,
+    /// Global shortcuts for the other actions. `nabu.toml` overrides these.
+    pub global_shortcuts: Vec<GlobalShortcut>,
+    pub keyboard_navigation: KeyboardNavigation,
+    pub theme_mode: ThemeMode,
+    pub color_theme: String,
+    pub paste_macros: Vec<PasteMacro>,
+    /// The panel's split, as percentages of its width.
+    pub left_panel_size: f64,
+    pub right_panel_size: f64,
+    pub right_panel_collapsed: bool,
+    pub filter_sidebar_collapsed: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            version: SETTINGS_VERSION,
+            auto_start: false,
+            hide_on_blur: true,
+            panel_placement: PanelPlacement::default(),
+            restore_clipboard: false,
+            join_separator: "\n".to_string(),
+            typing_delay_ms: 0,
+            paste_rules: Vec::new(),
+            external_commands: Vec::new(),
+            external_editor: String::new(),
+            toggle_shortcut: KeyBinding {
+                modifiers: vec!["ctrl".to_string(), "shift".to_string()],
+                key: "Space".to_string(),
+            },
+            global_shortcuts: Vec::new(),
+            keyboard_navigation: KeyboardNavigation::default(),
+            theme_mode: ThemeMode::default(),
+            color_theme: "vercel".to_string(),
+            paste_macros: Vec::new(),
+            left_panel_size: 40.0,
+            right_panel_size: 60.0,
+            right_panel_collapsed: false,
+            filter_sidebar_collapsed: false,
+        }
+    }
+}
+
+impl Settings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.version != SETTINGS_VERSION {
+            return Err(format!(
+                "Unsupported settings version {} (expected {})",
+                self.version, SETTINGS_VERSION
+            ));
+        }
+
+        if self.typing_delay_ms > MAX_TYPING_DELAY_MS {
+            return Err(format!(
+                "The typing delay can be at most {} ms",
+                MAX_TYPING_DELAY_MS
+            ));
+        }
+
+        if self
+            .paste_rules
+            .iter()
+            .any(|rule| rule.app.trim().is_empty())
+        {
+            return Err("Paste rules need an app".into());
+        }
+
+        for (i, command) in self.external_commands.iter().enumerate() {
+            if command.name.trim().is_empty() {
+                return Err(format!("External command {} has no name", i + 1));
+            }
+            if self.external_commands[..i]
+                .iter()
+                .any(|other| other.name == command.name)
+            {
+                return Err(format!("External command {} is listed twice", command.name));
+            }
+            if split_command_line(&command.command_line)?.is_empty() {
+                return Err(format!("External command {} is empty", command.name));
+            }
+        }
+
+        split_command_line(&self.external_editor)
+            .map_err(|e| format!("Invalid external editor: {}", e))?;
+
+        for (i, shortcut) in self.global_shortcuts.iter().enumerate() {
+            if shortcut.action == ShortcutAction::ToggleVisibility {
+                return Err("The panel shortcut is set with toggleShortcut".into());
+            }
+            if self.global_shortcuts[..i]
+                .iter()
+                .any(|other| other.action == shortcut.action)
+            {
+                return Err(format!("{:?} has two shortcuts", shortcut.action));
+            }
+            if let ShortcutAction::RunMacro(id) = shortcut.action {
+                if !self
+                    .paste_macros
+                    .iter()
+                    .any(|paste_macro| paste_macro.id == id)
+                {
+                    return Err(format!("The shortcut for macro id {} has no macro", id));
+                }
+            }
+            parse_accelerator(&shortcut.accelerator)?;
+        }
+
+        for (i, paste_macro) in self.paste_macros.iter().enumerate() {
+            if paste_macro.name.trim().is_empty() {
+                return Err(format!("Macro {} has no name", i + 1));
+            }
+            if self.paste_macros[..i]
+                .iter()
+                .any(|other| other.id == paste_macro.id)
+            {
+                return Err(format!("Macro id {} is used twice", paste_macro.id));
+            }
+            paste_macro
+                .validate()
+                .map_err(|e| format!("Macro {:?}: {}", paste_macro.name, e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns these settings with the fields in `patch` replaced.
+    pub fn patched(&self, patch: Map<String, Value>) -> Result<Settings, String> {
+        let Value::Object(mut fields) = serde_json::to_value(self).map_err(|e| e.to_string())?
+        else {
+            unreachable!("Settings serializes to an object");
+        };
+        for (key, value) in patch {
+            if !fields.contains_key(&key) {
+                return Err(format!("Unknown setting: {}", key));
+            }
+            fields.insert(key, value);
+        }
+
+        let settings: Settings = serde_json::from_value(Value::Object(fields))
+            .map_err(|e| format!("Invalid settings: {}", e))?;
+        settings.validate()?;
+        Ok(settings)
+    }
+}
+
+pub struct SettingsStore {
+    settings: Mutex<Settings>,
+}
+
+impl SettingsStore {
+    pub fn get(&self) -> Settings {
+        self.settings.lock().unwrap().clone()
+    }
+}
+
+fn settings_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    app_handle
+        .path()
+        .app_config_dir()
+        .map(|dir| dir.join(SETTINGS_FILE))
+        .map_err(|e| e.to_string())
+}
+
+fn save_settings(app_handle: &AppHandle, settings: &Settings) -> Result<(), String> {
+    let path = settings_path(app_handle)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    }
+    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+/// The panel's old settings moved after settings `version`, as a patch for
+/// [`Settings`]. Values there are strings: JSON for lists and objects,
+/// `true`/`false` for flags, and plain text otherwise.
+fn legacy_settings(app_handle: &AppHandle, version: u32) -> Map<String, Value> {
+    let rows = tauri::async_runtime::block_on(app_handle.state::<History>().settings())
+        .unwrap_or_default();
+    let defaults = serde_json::to_value(Settings::default()).unwrap_or_default();
+
+    rows.into_iter()
+        .filter_map(|(key, value)| {
+            let (_, field, _) = LEGACY_KEYS
+                .iter()
+                .find(|(legacy, _, moved_in)| *legacy == key && *moved_in > version)?;
+            let value = match defaults.get(*field)? {
+                Value::Bool(_) => Value::Bool(value == "true"),
+                Value::String(_) => Value::String(value),
+                _ => serde_json::from_str(&value).ok()?,
+            };
+            Some((field.to_string(), value))
+        })
+        .collect()
+}
+
+/// Brings `settings` from an older version up to date, copying over the panel's
+/// settings that have moved since. Old values that are no longer valid are
+/// skipped one by one.
+fn migrate(app_handle: &AppHandle, mut settings: Settings) -> Settings {
+    let version = settings.version;
+    settings.version = SETTINGS_VERSION;
+    for (field, value) in legacy_settings(app_handle, version) {
+        match settings.patched(Map::from_iter([(field.clone(), value)])) {
+            Ok(patched) => settings = patched,
+            Err(e) => log::warn!("Not migrating the old {} setting: {}", field, e),
+        }
+    }
+    settings
+}
+
+/// Loads the settings file, or on first run, the settings the panel stored in
+/// the history database. An invalid file is reported and defaults are used
+/// instead; the file is only overwritten once settings are changed in the app.
+pub fn init_settings(app_handle: &AppHandle) -> SettingsStore {
+    let path = settings_path(app_handle).ok();
+    let settings = match path.as_ref().map(std::fs::read_to_string) {
+        Some(Ok(json)) => serde_json::from_str::<Settings>(&json)
+            .map_err(|e| e.to_string())
+            .and_then(|settings| match settings.version {
+                version if version < SETTINGS_VERSION => {
+                    let settings = migrate(app_handle, settings);
+                    let _ = save_settings(app_handle, &settings);
+                    Ok(settings)
+                }
+                _ => settings.validate().map(|_| settings),
+            })
+            .unwrap_or_else(|e| {
+                log::warn!("Ignoring invalid settings in {:?}: {}", path, e);
+                Settings::default()
+            }),
+        _ => {
+            let settings = migrate(
+                app_handle,
+                Settings {
+                    version: 0,
+                    ..Settings::default()
+                },
+            );
+            let _ = save_settings(app_handle, &settings);
+            settings
+        }
+    };
+
+    SettingsStore {
+        settings: Mutex::new(settings),
+    }
+}
+
+/// Calls `f` with the new settings whenever they change.
+pub fn on_settings_changed<F>(app_handle: &AppHandle, f: F)
+where
+    F: Fn(&AppHandle, &Settings) + Send + 'static,
+{
+    let app_clone = app_handle.clone();
+    app_handle.listen("settings-changed", move |event| {
+        if let Ok(settings) = serde_json::from_str::<Settings>(event.payload()) {
+            f(&app_clone, &settings);
+        }
+    });
+}
+
+/// Keeps the system's login item in line with `auto_start`.
+pub fn sync_auto_start(app_handle: &AppHandle) {
+    use tauri_plugin_autostart::ManagerExt;
+
+    on_settings_changed(app_handle, |app_handle, settings| {
+        let autolaunch = app_handle.autolaunch();
+        if autolaunch.is_enabled().ok() != Some(settings.auto_start) {
+            let (result, change) = if settings.auto_start {
+                (autolaunch.enable(), "enable")
+            } else {
+                (autolaunch.disable(), "disable")
+            };
+            if let Err(e) = result {
+                log::warn!("Failed to {} starting at login: {}", change, e);
+            }
+        }
+    });
+}
+
+/// Changes the settings with `f`, holding the store's lock from reading them to
+/// saving the result so that changes made at the same time can't undo each
+/// other. New settings are validated and saved, then `settings-changed` is
+/// emitted to the backend and every window.
+pub fn modify_settings<F>(app_handle: &AppHandle, f: F) -> Result<Settings, String>
+where
+    F: FnOnce(&Settings) -> Result<Settings, String>,
+{
+    let store = app_handle.state::<SettingsStore>();
+    let mut current = store.settings.lock().unwrap();
+    let settings = f(&current)?;
+    if settings == *current {
+        return Ok(settings);
+    }
+    settings.validate()?;
+    save_settings(app_handle, &settings)?;
+    *current = settings.clone();
+    // Listeners may read the store themselves.
+    drop(current);
+
+    app_handle
+        .emit("settings-changed", &settings)
+        .map_err(|e| e.to_string())?;
+    Ok(settings)
+}
+
+#[command]
+pub fn get_settings(state: State<SettingsStore>) -> Settings {
+    state.get()
+}
+
+/// Changes the settings named in `patch`, e.g. `{ "hideOnBlur": false }`.
+/// Shortcuts have their own commands, which also register them.
+#[command]
+pub fn update_settings(app: AppHandle, patch: Map<String, Value>) -> Result<Settings, String> {
+    for (field, command) in [
+        ("toggleShortcut", "change_shortcut"),
+        ("globalShortcuts", "set_global_shortcut"),
+    ] {
+        if patch.contains_key(field) {
+            return Err(format!("{} is changed with {}", field, command));
+        }
+    }
+    modify_settings(&app, |settings| settings.patched(patch))
+}
@@ -0,0 +1,26 @@
+use tauri::{command, AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+/// The frontend renders only the settings page in the window with this label.
+const SETTINGS_LABEL: &str = "settings";
+
+/// Opens the settings window, or brings it to the front if it is already open.
+pub fn open_settings_window(app_handle: &AppHandle) -> tauri::Result<()> {
+    if let Some(window) = app_handle.get_webview_window(SETTINGS_LABEL) {
+        window.unminimize()?;
+        window.show()?;
+        return window.set_focus();
+    }
+
+    let window = WebviewWindowBuilder::new(app_handle, SETTINGS_LABEL, WebviewUrl::default())
+        .title("Nabu Settings")
+        .inner_size(640.0, 720.0)
+        .min_inner_size(480.0, 400.0)
+        .center()
+        .build()?;
+    window.set_focus()
+}
+
+#[command]
+pub fn open_settings(app: AppHandle) -> Result<(), String> {
+    open_settings_window(&app).map_err(|e| format!("Failed to open settings: {}", e))
+}
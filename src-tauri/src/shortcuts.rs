@@ -1,9 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
-use tauri::{command, AppHandle, Emitter, State};
+use std::time::Duration;
+use tauri::{command, AppHandle, Emitter, Manager};
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
+use tokio::time::sleep;
+
+use crate::config::{ConfigState, ShortcutBindings, CONFIG_FILE};
+use crate::settings::{modify_settings, GlobalShortcut, KeyBinding, Settings, SettingsStore};
+use crate::typing::{escape_shortcut, TypingState};
+
+/// How long the second stage of a chord stays registered after the first fires.
+pub const CHORD_TIMEOUT: Duration = Duration::from_millis(1500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    ToggleVisibility,
+    PastePlain,
+    PasteNext,
+    RunMacro(u32),
+}
+
+/// A single-chord shortcut bound to an action other than the panel toggle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActionShortcut {
+    pub shortcut: Shortcut,
+    pub action: ShortcutAction,
+}
+
+/// A two-stage shortcut such as `Ctrl+K` then `V`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChordShortcut {
+    pub first: Shortcut,
+    pub second: Shortcut,
+    pub action: ShortcutAction,
+}
+
+struct PendingChord {
+    first: Shortcut,
+    second_stage: Vec<Shortcut>,
+    generation: u64,
+}
 
 pub struct AppState {
     pub current_shortcut: Mutex<Shortcut>,
+    pub action_shortcuts: Mutex<Vec<ActionShortcut>>,
+    pub chords: Mutex<Vec<ChordShortcut>>,
+    pending_chord: Mutex<Option<PendingChord>>,
+    chord_generation: AtomicU64,
+}
+
+impl AppState {
+    /// Shortcuts that stay registered regardless of chord progress.
+    fn is_permanent(&self, shortcut: &Shortcut) -> bool {
+        *self.current_shortcut.lock().unwrap() == *shortcut
+            || self.is_action_shortcut(shortcut)
+            || self.is_chord_start(shortcut)
+    }
+
+    fn is_action_shortcut(&self, shortcut: &Shortcut) -> bool {
+        self.action_shortcuts
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|bound| bound.shortcut == *shortcut)
+    }
+
+    fn is_chord_start(&self, shortcut: &Shortcut) -> bool {
+        self.chords
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|chord| chord.first == *shortcut)
+    }
 }
 
 pub fn default_shortcut() -> Shortcut {
@@ -13,38 +83,682 @@ pub fn default_shortcut() -> Shortcut {
 pub fn init_shortcut_state() -> AppState {
     AppState {
         current_shortcut: Mutex::new(default_shortcut()),
+        action_shortcuts: Mutex::new(Vec::new()),
+        chords: Mutex::new(Vec::new()),
+        pending_chord: Mutex::new(None),
+        chord_generation: AtomicU64::new(0),
     }
 }
 
 #[cfg(desktop)]
 pub fn setup_shortcut_handler(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    let shortcut_app_handle = app_handle.clone();
-
     app_handle.plugin(
         tauri_plugin_global_shortcut::Builder::new()
-            .with_handler(move |_app, _shortcut, event| {
+            .with_handler(|app_handle, shortcut, event| {
                 if let ShortcutState::Pressed = event.state() {
-                    if let Ok(was_shown) = crate::visibility::toggle_visibility(&shortcut_app_handle) {
-                        if was_shown {
-                            let _ = shortcut_app_handle.emit("window-triggered-by-shortcut", ());
-                        }
-                    }
+                    handle_shortcut(app_handle, shortcut);
                 }
             })
             .build(),
     )?;
 
     app_handle.global_shortcut().register(default_shortcut())?;
+    if let Err(e) = saved_toggle(app_handle).and_then(|shortcut| bind_toggle(app_handle, shortcut))
+    {
+        log::warn!("Keeping the default panel shortcut: {}", e);
+    }
+
+    let saved = app_handle.state::<SettingsStore>().get().global_shortcuts;
+    let overrides = app_handle.state::<ConfigState>().get().shortcuts;
+    if let Err(e) = sync_action_shortcuts(app_handle, &saved, &overrides) {
+        log::warn!("Failed to bind global shortcuts: {}", e);
+    }
+    Ok(())
+}
+
+/// The panel toggle chosen in the settings window.
+pub fn saved_toggle(app_handle: &AppHandle) -> Result<Shortcut, String> {
+    let binding = app_handle.state::<SettingsStore>().get().toggle_shortcut;
+    parse_shortcut(&binding.modifiers, &binding.key)
+}
+
+fn handle_shortcut(app_handle: &AppHandle, shortcut: &Shortcut) {
+    let typing = app_handle.state::<TypingState>();
+    if typing.is_typing() && *shortcut == escape_shortcut() {
+        typing.cancel();
+        return;
+    }
+
+    let state = app_handle.state::<AppState>();
+
+    let completed = {
+        let mut pending = state.pending_chord.lock().unwrap();
+        let action = pending.as_ref().and_then(|pending| {
+            state
+                .chords
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|chord| chord.first == pending.first && chord.second == *shortcut)
+                .map(|chord| chord.action)
+        });
+        action.map(|action| (action, pending.take().unwrap()))
+    };
+
+    if let Some((action, pending)) = completed {
+        release_second_stage(app_handle, pending.second_stage);
+        run_action(app_handle, action);
+        return;
+    }
+
+    if *state.current_shortcut.lock().unwrap() == *shortcut {
+        run_action(app_handle, ShortcutAction::ToggleVisibility);
+        return;
+    }
+
+    let bound_action = state
+        .action_shortcuts
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|bound| bound.shortcut == *shortcut)
+        .map(|bound| bound.action);
+    if let Some(action) = bound_action {
+        run_action(app_handle, action);
+        return;
+    }
+
+    begin_chord(app_handle, *shortcut);
+}
+
+fn run_action(app_handle: &AppHandle, action: ShortcutAction) {
+    match action {
+        ShortcutAction::ToggleVisibility => {
+            if let Ok(was_shown) = crate::visibility::toggle_visibility(app_handle) {
+                if was_shown {
+                    let _ = app_handle.emit("window-triggered-by-shortcut", ());
+                }
+            }
+        }
+        ShortcutAction::PastePlain => {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = crate::paste::paste_plain_clipboard(&app_handle).await;
+            });
+        }
+        ShortcutAction::PasteNext => {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = crate::paste_queue::paste_next_entry(&app_handle).await;
+            });
+        }
+        ShortcutAction::RunMacro(id) => {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = crate::macros::run_macro_by_id(&app_handle, id, false).await;
+            });
+        }
+    }
+}
+
+fn begin_chord(app_handle: &AppHandle, first: Shortcut) {
+    let state = app_handle.state::<AppState>();
+
+    let second_stage: Vec<Shortcut> = state
+        .chords
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|chord| chord.first == first)
+        .map(|chord| chord.second)
+        .collect();
+
+    if second_stage.is_empty() {
+        return;
+    }
+
+    let generation = state.chord_generation.fetch_add(1, Ordering::SeqCst) + 1;
+    let previous = state.pending_chord.lock().unwrap().replace(PendingChord {
+        first,
+        second_stage: second_stage.clone(),
+        generation,
+    });
+
+    // The global shortcut plugin calls our handler while holding its registry
+    // lock, so second-stage keys have to be (un)registered off this thread.
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Some(previous) = previous {
+            unregister_second_stage(&app_handle, &previous.second_stage);
+        }
+
+        let global_shortcut = app_handle.global_shortcut();
+        for shortcut in &second_stage {
+            if !global_shortcut.is_registered(*shortcut) {
+                let _ = global_shortcut.register(*shortcut);
+            }
+        }
+
+        sleep(CHORD_TIMEOUT).await;
+
+        let state = app_handle.state::<AppState>();
+        let expired = {
+            let mut pending = state.pending_chord.lock().unwrap();
+            match pending.as_ref() {
+                Some(current) if current.generation == generation => pending.take(),
+                _ => None,
+            }
+        };
+
+        if let Some(expired) = expired {
+            unregister_second_stage(&app_handle, &expired.second_stage);
+        }
+    });
+}
+
+fn release_second_stage(app_handle: &AppHandle, second_stage: Vec<Shortcut>) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        unregister_second_stage(&app_handle, &second_stage);
+    });
+}
+
+fn unregister_second_stage(app_handle: &AppHandle, second_stage: &[Shortcut]) {
+    let state = app_handle.state::<AppState>();
+    for shortcut in second_stage {
+        if !state.is_permanent(shortcut) {
+            let _ = app_handle.global_shortcut().unregister(*shortcut);
+        }
+    }
+}
+
+/// Rebinds the panel toggle to `shortcut`, keeping the old binding if that fails.
+pub fn bind_toggle(app_handle: &AppHandle, shortcut: Shortcut) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+    // The plugin holds its registry lock while calling `handle_shortcut`, which
+    // reads the current shortcut, so that lock must not be held across plugin calls.
+    let current = *state.current_shortcut.lock().unwrap();
+    if current == shortcut {
+        return Ok(());
+    }
+    if state.is_action_shortcut(&shortcut) || state.is_chord_start(&shortcut) {
+        return Err("Shortcut is already bound to another action".into());
+    }
+
+    if let Err(e) = app_handle.global_shortcut().unregister(current) {
+        return Err(format!("Failed to unregister old shortcut: {}", e));
+    }
+
+    if let Err(e) = app_handle.global_shortcut().register(shortcut) {
+        let _ = app_handle.global_shortcut().register(current);
+        return Err(format!("Failed to register new shortcut: {}", e));
+    }
+
+    *state.current_shortcut.lock().unwrap() = shortcut;
     Ok(())
 }
 
+pub fn bind_action(
+    app_handle: &AppHandle,
+    shortcut: Shortcut,
+    action: ShortcutAction,
+) -> Result<(), String> {
+    if action == ShortcutAction::ToggleVisibility {
+        return Err("Use change_shortcut to rebind the panel toggle".into());
+    }
+
+    let state = app_handle.state::<AppState>();
+    let already_bound = state
+        .action_shortcuts
+        .lock()
+        .unwrap()
+        .contains(&ActionShortcut { shortcut, action });
+    if already_bound {
+        return Ok(());
+    }
+    if state.is_permanent(&shortcut) {
+        return Err("Shortcut is already bound to another action".into());
+    }
+
+    app_handle
+        .global_shortcut()
+        .register(shortcut)
+        .map_err(|e| format!("Failed to register shortcut: {}", e))?;
+
+    let replaced = {
+        let mut action_shortcuts = state.action_shortcuts.lock().unwrap();
+        let replaced = action_shortcuts
+            .iter()
+            .position(|bound| bound.action == action)
+            .map(|index| action_shortcuts.remove(index));
+        action_shortcuts.push(ActionShortcut { shortcut, action });
+        replaced
+    };
+
+    if let Some(replaced) = replaced {
+        if !state.is_permanent(&replaced.shortcut) {
+            let _ = app_handle.global_shortcut().unregister(replaced.shortcut);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn unbind_action(app_handle: &AppHandle, action: ShortcutAction) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+    let removed = {
+        let mut action_shortcuts = state.action_shortcuts.lock().unwrap();
+        action_shortcuts
+            .iter()
+            .position(|bound| bound.action == action)
+            .map(|index| action_shortcuts.remove(index))
+    };
+
+    if let Some(removed) = removed {
+        if !state.is_permanent(&removed.shortcut) {
+            app_handle
+                .global_shortcut()
+                .unregister(removed.shortcut)
+                .map_err(|e| format!("Failed to unregister shortcut: {}", e))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn bind_chord(
+    app_handle: &AppHandle,
+    first: Shortcut,
+    second: Shortcut,
+    action: ShortcutAction,
+) -> Result<(), String> {
+    if first == second {
+        return Err("Chord stages must use different keys".into());
+    }
+
+    let state = app_handle.state::<AppState>();
+    // A chord whose first stage is also a single shortcut could never be
+    // reached, since `handle_shortcut` runs the single shortcut instead.
+    if *state.current_shortcut.lock().unwrap() == first || state.is_action_shortcut(&first) {
+        return Err("The first key of the chord is already bound to another action".into());
+    }
+
+    let global_shortcut = app_handle.global_shortcut();
+    if !global_shortcut.is_registered(first) {
+        global_shortcut
+            .register(first)
+            .map_err(|e| format!("Failed to register chord shortcut: {}", e))?;
+    }
+
+    let replaced = {
+        let mut chords = state.chords.lock().unwrap();
+        let replaced = chords
+            .iter()
+            .position(|chord| chord.action == action)
+            .map(|index| chords.remove(index));
+        chords.push(ChordShortcut {
+            first,
+            second,
+            action,
+        });
+        replaced
+    };
+
+    if let Some(replaced) = replaced {
+        if !state.is_permanent(&replaced.first) {
+            let _ = global_shortcut.unregister(replaced.first);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn unbind_chord(app_handle: &AppHandle, action: ShortcutAction) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+    let removed = {
+        let mut chords = state.chords.lock().unwrap();
+        chords
+            .iter()
+            .position(|chord| chord.action == action)
+            .map(|index| chords.remove(index))
+    };
+
+    if let Some(removed) = removed {
+        if !state.is_permanent(&removed.first) {
+            app_handle
+                .global_shortcut()
+                .unregister(removed.first)
+                .map_err(|e| format!("Failed to unregister chord shortcut: {}", e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The stages `action` is bound to right now: one shortcut, or two for a chord.
+fn bound_stages(state: &AppState, action: ShortcutAction) -> Option<Vec<Shortcut>> {
+    let single = state
+        .action_shortcuts
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|bound| bound.action == action)
+        .map(|bound| vec![bound.shortcut]);
+    single.or_else(|| {
+        state
+            .chords
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|chord| chord.action == action)
+            .map(|chord| vec![chord.first, chord.second])
+    })
+}
+
+/// Binds the action shortcuts saved in the settings window, with the ones set in
+/// the config file taking precedence, and unbinds actions that have neither.
+/// Every action is tried; the errors are returned together.
+pub fn sync_action_shortcuts(
+    app_handle: &AppHandle,
+    saved: &[GlobalShortcut],
+    overrides: &ShortcutBindings,
+) -> Result<(), String> {
+    let mut wanted = saved
+        .iter()
+        .map(|shortcut| Ok((shortcut.action, parse_accelerator(&shortcut.accelerator)?)))
+        .collect::<Result<Vec<_>, String>>()?;
+    for (action, binding) in overrides.actions() {
+        if let Some(binding) = binding {
+            wanted.retain(|(other, _)| *other != action);
+            wanted.push((action, parse_accelerator(binding)?));
+        }
+    }
+
+    let state = app_handle.state::<AppState>();
+    let wanted_stages = |action: ShortcutAction| {
+        wanted
+            .iter()
+            .find(|(other, _)| *other == action)
+            .map(|(_, stages)| stages.clone())
+    };
+
+    // Unbind everything that changes first, so two actions can swap shortcuts.
+    let bound: Vec<ShortcutAction> = {
+        let singles = state.action_shortcuts.lock().unwrap();
+        let chords = state.chords.lock().unwrap();
+        singles
+            .iter()
+            .map(|bound| bound.action)
+            .chain(chords.iter().map(|chord| chord.action))
+            .collect()
+    };
+    for action in bound {
+        if wanted_stages(action) != bound_stages(&state, action) {
+            unbind_action(app_handle, action)?;
+            unbind_chord(app_handle, action)?;
+        }
+    }
+
+    let mut errors = Vec::new();
+    for (action, stages) in &wanted {
+        if bound_stages(&state, *action).as_ref() == Some(stages) {
+            continue;
+        }
+        let result = match stages[..] {
+            [shortcut] => bind_action(app_handle, shortcut, *action),
+            [first, second] => bind_chord(app_handle, first, second, *action),
+            _ => unreachable!("parse_accelerator returns one or two stages"),
+        };
+        if let Err(e) = result {
+            errors.push(e);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Binds and saves the panel toggle chosen in the settings window, which is
+/// refused while the config file sets one.
 #[command]
 pub fn change_shortcut(
     app_handle: AppHandle,
     modifiers: Vec<String>,
     key: String,
-    state: State<AppState>,
 ) -> Result<(), String> {
+    if app_handle
+        .state::<ConfigState>()
+        .get()
+        .shortcuts
+        .toggle
+        .is_some()
+    {
+        return Err(format!("The panel shortcut is set in {}", CONFIG_FILE));
+    }
+    bind_toggle(&app_handle, parse_shortcut(&modifiers, &key)?)?;
+
+    modify_settings(&app_handle, |settings| {
+        Ok(Settings {
+            toggle_shortcut: KeyBinding { modifiers, key },
+            ..settings.clone()
+        })
+    })
+    .map(|_| ())
+}
+
+/// Binds and saves a global shortcut from the settings window, or removes it
+/// with `None`. Refused for actions the config file binds.
+#[command]
+pub fn set_global_shortcut(
+    app_handle: AppHandle,
+    action: ShortcutAction,
+    accelerator: Option<String>,
+) -> Result<(), String> {
+    let overrides = app_handle.state::<ConfigState>().get().shortcuts;
+    if overrides
+        .actions()
+        .iter()
+        .any(|(bound, binding)| *bound == action && binding.is_some())
+    {
+        return Err(format!("This shortcut is set in {}", CONFIG_FILE));
+    }
+
+    let with_shortcut = |settings: &Settings| {
+        let mut settings = settings.clone();
+        settings
+            .global_shortcuts
+            .retain(|shortcut| shortcut.action != action);
+        if let Some(accelerator) = &accelerator {
+            settings.global_shortcuts.push(GlobalShortcut {
+                action,
+                accelerator: accelerator.clone(),
+            });
+        }
+        settings.validate().map(|_| settings)
+    };
+
+    let previous = app_handle.state::<SettingsStore>().get();
+    let wanted = with_shortcut(&previous)?;
+    if let Err(e) = sync_action_shortcuts(&app_handle, &wanted.global_shortcuts, &overrides) {
+        let _ = sync_action_shortcuts(&app_handle, &previous.global_shortcuts, &overrides);
+        return Err(e);
+    }
+    modify_settings(&app_handle, with_shortcut).map(|_| ())
+}
+
+/// Parses a shortcut written like `ctrl+shift+space`, or a chord written as two
+/// such shortcuts separated by a space, like `ctrl+k v`.
+pub fn parse_accelerator(text: &str) -> Result<Vec<Shortcut>, String> {
+    let stages = text
+        .split_whitespace()
+        .map(|stage| {
+            let mut parts: Vec<&str> = stage.split('+').collect();
+            let key = parts.pop().unwrap_or_default();
+            let modifiers: Vec<String> = parts
+                .iter()
+                .map(|modifier| match modifier.to_lowercase().as_str() {
+                    "control" => "ctrl".to_string(),
+                    "cmd" | "super" => "meta".to_string(),
+                    "option" => "alt".to_string(),
+                    other => other.to_string(),
+                })
+                .collect();
+            parse_shortcut(&modifiers, &key_code_name(key))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match stages.len() {
+        1 | 2 => Ok(stages),
+        _ => Err(format!("Invalid shortcut: {:?}", text)),
+    }
+}
+
+/// The key codes shortcuts can use, by the names the panel sends.
+const KEY_CODES: &[(&str, Code)] = &[
+    ("KeyA", Code::KeyA),
+    ("KeyB", Code::KeyB),
+    ("KeyC", Code::KeyC),
+    ("KeyD", Code::KeyD),
+    ("KeyE", Code::KeyE),
+    ("KeyF", Code::KeyF),
+    ("KeyG", Code::KeyG),
+    ("KeyH", Code::KeyH),
+    ("KeyI", Code::KeyI),
+    ("KeyJ", Code::KeyJ),
+    ("KeyK", Code::KeyK),
+    ("KeyL", Code::KeyL),
+    ("KeyM", Code::KeyM),
+    ("KeyN", Code::KeyN),
+    ("KeyO", Code::KeyO),
+    ("KeyP", Code::KeyP),
+    ("KeyQ", Code::KeyQ),
+    ("KeyR", Code::KeyR),
+    ("KeyS", Code::KeyS),
+    ("KeyT", Code::KeyT),
+    ("KeyU", Code::KeyU),
+    ("KeyV", Code::KeyV),
+    ("KeyW", Code::KeyW),
+    ("KeyX", Code::KeyX),
+    ("KeyY", Code::KeyY),
+    ("KeyZ", Code::KeyZ),
+    ("Digit0", Code::Digit0),
+    ("Digit1", Code::Digit1),
+    ("Digit2", Code::Digit2),
+    ("Digit3", Code::Digit3),
+    ("Digit4", Code::Digit4),
+    ("Digit5", Code::Digit5),
+    ("Digit6", Code::Digit6),
+    ("Digit7", Code::Digit7),
+    ("Digit8", Code::Digit8),
+    ("Digit9", Code::Digit9),
+    ("F1", Code::F1),
+    ("F2", Code::F2),
+    ("F3", Code::F3),
+    ("F4", Code::F4),
+    ("F5", Code::F5),
+    ("F6", Code::F6),
+    ("F7", Code::F7),
+    ("F8", Code::F8),
+    ("F9", Code::F9),
+    ("F10", Code::F10),
+    ("F11", Code::F11),
+    ("F12", Code::F12),
+    ("Space", Code::Space),
+    ("Tab", Code::Tab),
+    ("Escape", Code::Escape),
+    ("Enter", Code::Enter),
+    ("Backspace", Code::Backspace),
+    ("ArrowUp", Code::ArrowUp),
+    ("ArrowDown", Code::ArrowDown),
+    ("ArrowLeft", Code::ArrowLeft),
+    ("ArrowRight", Code::ArrowRight),
+    ("Delete", Code::Delete),
+    ("Insert", Code::Insert),
+    ("Home", Code::Home),
+    ("End", Code::End),
+    ("PageUp", Code::PageUp),
+    ("PageDown", Code::PageDown),
+    ("CapsLock", Code::CapsLock),
+    ("ScrollLock", Code::ScrollLock),
+    ("NumLock", Code::NumLock),
+    ("PrintScreen", Code::PrintScreen),
+    ("Pause", Code::Pause),
+    ("Numpad0", Code::Numpad0),
+    ("Numpad1", Code::Numpad1),
+    ("Numpad2", Code::Numpad2),
+    ("Numpad3", Code::Numpad3),
+    ("Numpad4", Code::Numpad4),
+    ("Numpad5", Code::Numpad5),
+    ("Numpad6", Code::Numpad6),
+    ("Numpad7", Code::Numpad7),
+    ("Numpad8", Code::Numpad8),
+    ("Numpad9", Code::Numpad9),
+    ("NumpadAdd", Code::NumpadAdd),
+    ("NumpadSubtract", Code::NumpadSubtract),
+    ("NumpadMultiply", Code::NumpadMultiply),
+    ("NumpadDivide", Code::NumpadDivide),
+    ("NumpadDecimal", Code::NumpadDecimal),
+    ("NumpadEnter", Code::NumpadEnter),
+    ("NumpadEqual", Code::NumpadEqual),
+    ("Semicolon", Code::Semicolon),
+    ("Equal", Code::Equal),
+    ("Comma", Code::Comma),
+    ("Minus", Code::Minus),
+    ("Period", Code::Period),
+    ("Slash", Code::Slash),
+    ("Backquote", Code::Backquote),
+    ("BracketLeft", Code::BracketLeft),
+    ("Backslash", Code::Backslash),
+    ("BracketRight", Code::BracketRight),
+    ("Quote", Code::Quote),
+];
+
+/// Other names accepted for keys in the config file, besides the codes in
+/// [`KEY_CODES`] in any case.
+const KEY_ALIASES: &[(&str, &str)] = &[
+    ("esc", "Escape"),
+    ("return", "Enter"),
+    ("up", "ArrowUp"),
+    ("down", "ArrowDown"),
+    ("left", "ArrowLeft"),
+    ("right", "ArrowRight"),
+    ("del", "Delete"),
+    ("ins", "Insert"),
+    ("pgup", "PageUp"),
+    ("pgdn", "PageDown"),
+    (";", "Semicolon"),
+    ("=", "Equal"),
+    (",", "Comma"),
+    ("-", "Minus"),
+    (".", "Period"),
+    ("/", "Slash"),
+    ("`", "Backquote"),
+    ("[", "BracketLeft"),
+    ("\\", "Backslash"),
+    ("]", "BracketRight"),
+    ("'", "Quote"),
+];
+
+/// Accepts `v` and `1` as well as the `KeyV` and `Digit1` codes the panel sends,
+/// and names in any case like `space` or `pageup`.
+fn key_code_name(key: &str) -> String {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => format!("Key{}", c.to_ascii_uppercase()),
+        (Some(c), None) if c.is_ascii_digit() => format!("Digit{}", c),
+        _ => KEY_CODES
+            .iter()
+            .map(|(name, _)| (*name, *name))
+            .chain(KEY_ALIASES.iter().copied())
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map_or_else(|| key.to_string(), |(_, code)| code.to_string()),
+    }
+}
+
+fn parse_shortcut(modifiers: &[String], key: &str) -> Result<Shortcut, String> {
     let mut modifier_flags = Modifiers::empty();
 
     for modifier in modifiers {
@@ -57,129 +771,120 @@ pub fn change_shortcut(
         }
     }
 
-    let code = parse_key_code(&key)?;
-    let new_shortcut = Shortcut::new(
+    let code = parse_key_code(key)?;
+    Ok(Shortcut::new(
         if modifier_flags.is_empty() {
             None
         } else {
             Some(modifier_flags)
         },
         code,
-    );
+    ))
+}
 
-    let mut current = state.current_shortcut.lock().unwrap();
+fn parse_key_code(key: &str) -> Result<Code, String> {
+    KEY_CODES
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, code)| *code)
+        .ok_or_else(|| format!("Unsupported key: {}", key))
+}
 
-    if let Err(e) = app_handle.global_shortcut().unregister(*current) {
-        return Err(format!("Failed to unregister old shortcut: {}", e));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shortcut(modifiers: Modifiers, code: Code) -> Shortcut {
+        Shortcut::new((!modifiers.is_empty()).then_some(modifiers), code)
     }
 
-    if let Err(e) = app_handle.global_shortcut().register(new_shortcut) {
-        let _ = app_handle.global_shortcut().register(*current);
-        return Err(format!("Failed to register new shortcut: {}", e));
+    #[test]
+    fn key_code_name_accepts_letters_digits_and_codes() {
+        assert_eq!(key_code_name("v"), "KeyV");
+        assert_eq!(key_code_name("V"), "KeyV");
+        assert_eq!(key_code_name("1"), "Digit1");
+        assert_eq!(key_code_name("KeyV"), "KeyV");
+        assert_eq!(key_code_name("Digit1"), "Digit1");
     }
 
-    *current = new_shortcut;
-    Ok(())
-}
+    #[test]
+    fn key_code_name_maps_multi_word_names() {
+        assert_eq!(key_code_name("space"), "Space");
+        assert_eq!(key_code_name("pageup"), "PageUp");
+        assert_eq!(key_code_name("PAGEDOWN"), "PageDown");
+        assert_eq!(key_code_name("arrowup"), "ArrowUp");
+        assert_eq!(key_code_name("numpad1"), "Numpad1");
+        assert_eq!(key_code_name("numpadenter"), "NumpadEnter");
+        assert_eq!(key_code_name("printscreen"), "PrintScreen");
+        assert_eq!(key_code_name("f12"), "F12");
+    }
 
-fn parse_key_code(key: &str) -> Result<Code, String> {
-    match key {
-        "KeyA" => Ok(Code::KeyA),
-        "KeyB" => Ok(Code::KeyB),
-        "KeyC" => Ok(Code::KeyC),
-        "KeyD" => Ok(Code::KeyD),
-        "KeyE" => Ok(Code::KeyE),
-        "KeyF" => Ok(Code::KeyF),
-        "KeyG" => Ok(Code::KeyG),
-        "KeyH" => Ok(Code::KeyH),
-        "KeyI" => Ok(Code::KeyI),
-        "KeyJ" => Ok(Code::KeyJ),
-        "KeyK" => Ok(Code::KeyK),
-        "KeyL" => Ok(Code::KeyL),
-        "KeyM" => Ok(Code::KeyM),
-        "KeyN" => Ok(Code::KeyN),
-        "KeyO" => Ok(Code::KeyO),
-        "KeyP" => Ok(Code::KeyP),
-        "KeyQ" => Ok(Code::KeyQ),
-        "KeyR" => Ok(Code::KeyR),
-        "KeyS" => Ok(Code::KeyS),
-        "KeyT" => Ok(Code::KeyT),
-        "KeyU" => Ok(Code::KeyU),
-        "KeyV" => Ok(Code::KeyV),
-        "KeyW" => Ok(Code::KeyW),
-        "KeyX" => Ok(Code::KeyX),
-        "KeyY" => Ok(Code::KeyY),
-        "KeyZ" => Ok(Code::KeyZ),
-        "Digit0" => Ok(Code::Digit0),
-        "Digit1" => Ok(Code::Digit1),
-        "Digit2" => Ok(Code::Digit2),
-        "Digit3" => Ok(Code::Digit3),
-        "Digit4" => Ok(Code::Digit4),
-        "Digit5" => Ok(Code::Digit5),
-        "Digit6" => Ok(Code::Digit6),
-        "Digit7" => Ok(Code::Digit7),
-        "Digit8" => Ok(Code::Digit8),
-        "Digit9" => Ok(Code::Digit9),
-        "F1" => Ok(Code::F1),
-        "F2" => Ok(Code::F2),
-        "F3" => Ok(Code::F3),
-        "F4" => Ok(Code::F4),
-        "F5" => Ok(Code::F5),
-        "F6" => Ok(Code::F6),
-        "F7" => Ok(Code::F7),
-        "F8" => Ok(Code::F8),
-        "F9" => Ok(Code::F9),
-        "F10" => Ok(Code::F10),
-        "F11" => Ok(Code::F11),
-        "F12" => Ok(Code::F12),
-        "Space" => Ok(Code::Space),
-        "Tab" => Ok(Code::Tab),
-        "Escape" => Ok(Code::Escape),
-        "Enter" => Ok(Code::Enter),
-        "Backspace" => Ok(Code::Backspace),
-        "ArrowUp" => Ok(Code::ArrowUp),
-        "ArrowDown" => Ok(Code::ArrowDown),
-        "ArrowLeft" => Ok(Code::ArrowLeft),
-        "ArrowRight" => Ok(Code::ArrowRight),
-        "Delete" => Ok(Code::Delete),
-        "Insert" => Ok(Code::Insert),
-        "Home" => Ok(Code::Home),
-        "End" => Ok(Code::End),
-        "PageUp" => Ok(Code::PageUp),
-        "PageDown" => Ok(Code::PageDown),
-        "CapsLock" => Ok(Code::CapsLock),
-        "ScrollLock" => Ok(Code::ScrollLock),
-        "NumLock" => Ok(Code::NumLock),
-        "PrintScreen" => Ok(Code::PrintScreen),
-        "Pause" => Ok(Code::Pause),
-        "Numpad0" => Ok(Code::Numpad0),
-        "Numpad1" => Ok(Code::Numpad1),
-        "Numpad2" => Ok(Code::Numpad2),
-        "Numpad3" => Ok(Code::Numpad3),
-        "Numpad4" => Ok(Code::Numpad4),
-        "Numpad5" => Ok(Code::Numpad5),
-        "Numpad6" => Ok(Code::Numpad6),
-        "Numpad7" => Ok(Code::Numpad7),
-        "Numpad8" => Ok(Code::Numpad8),
-        "Numpad9" => Ok(Code::Numpad9),
-        "NumpadAdd" => Ok(Code::NumpadAdd),
-        "NumpadSubtract" => Ok(Code::NumpadSubtract),
-        "NumpadMultiply" => Ok(Code::NumpadMultiply),
-        "NumpadDivide" => Ok(Code::NumpadDivide),
-        "NumpadDecimal" => Ok(Code::NumpadDecimal),
-        "NumpadEnter" => Ok(Code::NumpadEnter),
-        "NumpadEqual" => Ok(Code::NumpadEqual),
-        "Semicolon" => Ok(Code::Semicolon),
-        "Equal" => Ok(Code::Equal),
-        "Comma" => Ok(Code::Comma),
-        "Minus" => Ok(Code::Minus),
-        "Period" => Ok(Code::Period),
-        "Slash" => Ok(Code::Slash),
-        "Backquote" => Ok(Code::Backquote),
-        "BracketLeft" => Ok(Code::BracketLeft),
-        "Backslash" => Ok(Code::Backslash),
-        "BracketRight" => Ok(Code::BracketRight),
-        "Quote" => Ok(Code::Quote),
-        _ => Err(format!("Unsupported key: {}", key)),
+    #[test]
+    fn key_code_name_maps_aliases_and_punctuation() {
+        assert_eq!(key_code_name("esc"), "Escape");
+        assert_eq!(key_code_name("up"), "ArrowUp");
+        assert_eq!(key_code_name("pgdn"), "PageDown");
+        assert_eq!(key_code_name(","), "Comma");
+        assert_eq!(key_code_name("\\"), "Backslash");
+    }
+
+    #[test]
+    fn key_code_name_leaves_unknown_keys_alone() {
+        assert_eq!(key_code_name("hyper"), "hyper");
+        assert_eq!(key_code_name("é"), "é");
+        assert_eq!(key_code_name(""), "");
+    }
+
+    #[test]
+    fn parses_single_shortcuts() {
+        assert_eq!(
+            parse_accelerator("ctrl+shift+space"),
+            Ok(vec![shortcut(
+                Modifiers::CONTROL | Modifiers::SHIFT,
+                Code::Space
+            )])
+        );
+        assert_eq!(
+            parse_accelerator("Control+Option+PageUp"),
+            Ok(vec![shortcut(
+                Modifiers::CONTROL | Modifiers::ALT,
+                Code::PageUp
+            )])
+        );
+        assert_eq!(
+            parse_accelerator("cmd+,"),
+            Ok(vec![shortcut(Modifiers::META, Code::Comma)])
+        );
+        assert_eq!(
+            parse_accelerator("f5"),
+            Ok(vec![shortcut(Modifiers::empty(), Code::F5)])
+        );
+    }
+
+    #[test]
+    fn parses_chords() {
+        assert_eq!(
+            parse_accelerator("ctrl+k v"),
+            Ok(vec![
+                shortcut(Modifiers::CONTROL, Code::KeyK),
+                shortcut(Modifiers::empty(), Code::KeyV),
+            ])
+        );
+        assert_eq!(
+            parse_accelerator("  super+k   shift+numpad1 "),
+            Ok(vec![
+                shortcut(Modifiers::META, Code::KeyK),
+                shortcut(Modifiers::SHIFT, Code::Numpad1),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_shortcuts() {
+        assert!(parse_accelerator("").is_err());
+        assert!(parse_accelerator("ctrl+k v x").is_err());
+        assert!(parse_accelerator("ctrl+").is_err());
+        assert!(parse_accelerator("hyper+a").is_err());
+        assert!(parse_accelerator("ctrl+é").is_err());
     }
 }
@@ -0,0 +1,294 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::Local;
+use enigo::{Direction, Key, Keyboard};
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::{command, AppHandle};
+use tokio::time::sleep;
+
+use crate::paste::{
+    clipboard_plain_text, with_enigo, write_and_paste, write_plain_text, PasteError,
+};
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+const DEFAULT_TIME_FORMAT: &str = "%H:%M";
+
+/// Gives the target application time to insert the paste before the caret is moved.
+const CURSOR_DELAY: Duration = Duration::from_millis(50);
+
+enum Token<'a> {
+    Text(&'a str),
+    Placeholder { name: &'a str, arg: Option<&'a str> },
+}
+
+/// Splits a snippet into literal text and `{name}` / `{name:arg}` placeholders.
+/// `{{` and `}}` stand for literal braces.
+fn tokenize(template: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        rest = &rest[start..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            tokens.push(Token::Text(&rest[..1]));
+            rest = &rest[2..];
+            continue;
+        }
+
+        let end = rest.find('}').filter(|_| rest.starts_with('{'));
+        match end {
+            Some(end) if !rest[1..end].contains('{') => {
+                let body = &rest[1..end];
+                let (name, arg) = match body.split_once(':') {
+                    Some((name, arg)) => (name, Some(arg)),
+                    None => (body, None),
+                };
+                tokens.push(Token::Placeholder { name, arg });
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                tokens.push(Token::Text(&rest[..1]));
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    tokens
+}
+
+/// A snippet with its placeholders filled in.
+pub struct Expansion {
+    pub text: String,
+    /// Where `{cursor}` was, in characters from the start of `text`.
+    pub cursor: Option<usize>,
+}
+
+impl Expansion {
+    /// How many characters the caret has to move back after pasting.
+    fn cursor_offset(&self) -> usize {
+        self.cursor
+            .map_or(0, |cursor| self.text.chars().count() - cursor)
+    }
+}
+
+fn format_now(format: &str) -> Result<String, String> {
+    // An invalid specifier would otherwise only fail, with a panic, when displayed.
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if items.contains(&Item::Error) {
+        return Err(format!("Invalid date format: {}", format));
+    }
+    Ok(Local::now().format_with_items(items.iter()).to_string())
+}
+
+/// Expands `template`. Unknown placeholders are left as they are, so snippets
+/// containing code or JSON don't need escaping.
+pub fn expand(
+    template: &str,
+    inputs: &HashMap<String, String>,
+    clipboard: impl Fn() -> Result<String, String>,
+) -> Result<Expansion, String> {
+    let mut text = String::new();
+    let mut cursor = None;
+
+    for token in tokenize(template) {
+        match token {
+            Token::Text(literal) => text.push_str(literal),
+            Token::Placeholder { name, arg } => match (name, arg) {
+                ("date", arg) => text.push_str(&format_now(arg.unwrap_or(DEFAULT_DATE_FORMAT))?),
+                ("time", arg) => text.push_str(&format_now(arg.unwrap_or(DEFAULT_TIME_FORMAT))?),
+                ("clipboard", None) => text.push_str(&clipboard()?),
+                ("uuid", None) => text.push_str(&uuid::Uuid::new_v4().to_string()),
+                ("cursor", None) => cursor = Some(text.chars().count()),
+                ("input", Some(field)) => {
+                    let value = inputs
+                        .get(field)
+                        .ok_or_else(|| format!("No value given for {{input:{}}}", field))?;
+                    text.push_str(value);
+                }
+                (name, Some(arg)) => {
+                    text.push_str(&format!("{{{}:{}}}", name, arg));
+                }
+                (name, None) => text.push_str(&format!("{{{}}}", name)),
+            },
+        }
+    }
+
+    Ok(Expansion { text, cursor })
+}
+
+/// The `{input:Name}` fields the user has to fill in, in order of appearance.
+#[command]
+pub fn snippet_inputs(template: String) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for token in tokenize(&template) {
+        if let Token::Placeholder {
+            name: "input",
+            arg: Some(field),
+        } = token
+        {
+            if !names.iter().any(|name| name == field) {
+                names.push(field.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Expands a snippet for previewing; `{cursor}` is dropped.
+#[command]
+pub fn expand_snippet(template: String, inputs: HashMap<String, String>) -> Result<String, String> {
+    expand(&template, &inputs, clipboard_plain_text).map(|expansion| expansion.text)
+}
+
+/// Expands a snippet and pastes it, leaving the caret at `{cursor}` if present.
+#[command]
+pub async fn paste_snippet(
+    app: AppHandle,
+    template: String,
+    inputs: HashMap<String, String>,
+    restore_clipboard: Option<bool>,
+) -> Result<(), PasteError> {
+    let restore_clipboard = restore_clipboard.unwrap_or(false);
+    let expansion = expand(&template, &inputs, clipboard_plain_text)?;
+
+    let cursor_offset = expansion.cursor_offset();
+    write_and_paste(&app, restore_clipboard, || write_plain_text(expansion.text)).await?;
+    move_cursor_back(&app, cursor_offset)
+        .await
+        .map_err(PasteError::from)
+}
+
+async fn move_cursor_back(app: &AppHandle, offset: usize) -> Result<(), String> {
+    if offset == 0 {
+        return Ok(());
+    }
+
+    sleep(CURSOR_DELAY).await;
+    with_enigo(app, move |enigo| {
+        for _ in 0..offset {
+            enigo
+                .key(Key::LeftArrow, Direction::Click)
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_text(template: &str) -> Result<String, String> {
+        expand(template, &HashMap::new(), || Ok("clip".to_string())).map(|e| e.text)
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        assert_eq!(expand_text("a {{b}} c}}"), Ok("a {b} c}".to_string()));
+        assert_eq!(expand_text("{{date}}"), Ok("{date}".to_string()));
+    }
+
+    #[test]
+    fn stray_braces_are_kept() {
+        assert_eq!(expand_text("if (x) { y }"), Ok("if (x) { y }".to_string()));
+        assert_eq!(expand_text("{a {b}"), Ok("{a {b}".to_string()));
+        assert_eq!(expand_text("} {"), Ok("} {".to_string()));
+    }
+
+    #[test]
+    fn unknown_placeholders_are_kept() {
+        assert_eq!(
+            expand_text("{foo} {bar:baz} {clipboard:x} {uuid:1}"),
+            Ok("{foo} {bar:baz} {clipboard:x} {uuid:1}".to_string())
+        );
+        assert_eq!(
+            expand_text(r#"{"key": 1}"#),
+            Ok(r#"{"key": 1}"#.to_string())
+        );
+    }
+
+    #[test]
+    fn fills_in_inputs() {
+        let inputs = HashMap::from([("Name".to_string(), "Ada".to_string())]);
+        let expansion = expand("Hi {input:Name}, {input:Name}!", &inputs, || {
+            Err("unused".to_string())
+        });
+        assert_eq!(expansion.map(|e| e.text), Ok("Hi Ada, Ada!".to_string()));
+
+        let missing = expand("{input:Other}", &inputs, || Err("unused".to_string()));
+        assert_eq!(
+            missing.err(),
+            Some("No value given for {input:Other}".to_string())
+        );
+    }
+
+    #[test]
+    fn lists_each_input_once_in_order() {
+        assert_eq!(
+            snippet_inputs("{input:B} {input:A} {input:B} {input} {{input:C}}".to_string()),
+            vec!["B".to_string(), "A".to_string()]
+        );
+    }
+
+    #[test]
+    fn inserts_the_clipboard() {
+        assert_eq!(expand_text("[{clipboard}]"), Ok("[clip]".to_string()));
+        let failed = expand("{clipboard}", &HashMap::new(), || Err("empty".to_string()));
+        assert_eq!(failed.err(), Some("empty".to_string()));
+    }
+
+    #[test]
+    fn counts_the_cursor_offset_in_characters() {
+        let expansion = expand("héllo {cursor}wörld 😀", &HashMap::new(), || {
+            Err("unused".to_string())
+        })
+        .unwrap();
+        assert_eq!(expansion.text, "héllo wörld 😀");
+        assert_eq!(expansion.cursor, Some(6));
+        assert_eq!(expansion.cursor_offset(), 7);
+
+        let at_end = expand(
+            "done{cursor}",
+            &HashMap::new(),
+            || Err("unused".to_string()),
+        );
+        assert_eq!(at_end.unwrap().cursor_offset(), 0);
+
+        let without = expand("no cursor", &HashMap::new(), || Err("unused".to_string()));
+        assert_eq!(without.unwrap().cursor_offset(), 0);
+    }
+
+    #[test]
+    fn formats_dates_and_times() {
+        let date = expand_text("{date}").unwrap();
+        assert_eq!(date.len(), 10);
+        assert!(chrono::NaiveDate::parse_from_str(&date, DEFAULT_DATE_FORMAT).is_ok());
+
+        let time = expand_text("{time:%H:%M:%S}").unwrap();
+        assert!(chrono::NaiveTime::parse_from_str(&time, "%H:%M:%S").is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_date_formats() {
+        assert_eq!(
+            expand_text("{date:%Q}"),
+            Err("Invalid date format: %Q".to_string())
+        );
+        assert!(expand_text("{time:%}").is_err());
+    }
+
+    #[test]
+    fn generates_uuids() {
+        let uuid = expand_text("{uuid}").unwrap();
+        assert!(uuid::Uuid::parse_str(&uuid).is_ok());
+        assert_ne!(expand_text("{uuid}").unwrap(), uuid);
+    }
+}
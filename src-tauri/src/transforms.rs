@@ -0,0 +1,424 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+use crate::entry::ClipboardEntry;
+
+/// A text transformation that can be applied to an entry before pasting or saving it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transform {
+    Uppercase,
+    Lowercase,
+    TitleCase,
+    SnakeCase,
+    CamelCase,
+    Trim,
+    /// Replaces every run of whitespace, including line breaks, with one space.
+    CollapseWhitespace,
+    SortLines,
+    DedupeLines,
+    CrlfToLf,
+    LfToCrlf,
+    UrlEncode,
+    UrlDecode,
+    Base64Encode,
+    Base64Decode,
+    JsonPretty,
+    JsonMinify,
+    HtmlEscape,
+}
+
+impl Transform {
+    pub fn apply(self, text: &str) -> Result<String, String> {
+        Ok(match self {
+            Transform::Uppercase => text.to_uppercase(),
+            Transform::Lowercase => text.to_lowercase(),
+            Transform::TitleCase => map_lines(text, title_case),
+            Transform::SnakeCase => map_lines(text, |line| words(line).join("_")),
+            Transform::CamelCase => map_lines(text, camel_case),
+            Transform::Trim => text.trim().to_string(),
+            Transform::CollapseWhitespace => text.split_whitespace().collect::<Vec<_>>().join(" "),
+            Transform::SortLines => edit_lines(text, |lines| lines.sort()),
+            Transform::DedupeLines => edit_lines(text, |lines| {
+                let mut seen = std::collections::HashSet::new();
+                lines.retain(|line| seen.insert(*line));
+            }),
+            Transform::CrlfToLf => text.replace("\r\n", "\n"),
+            Transform::LfToCrlf => text.replace("\r\n", "\n").replace('\n', "\r\n"),
+            Transform::UrlEncode => url_encode(text),
+            Transform::UrlDecode => url_decode(text)?,
+            Transform::Base64Encode => STANDARD.encode(text),
+            Transform::Base64Decode => {
+                let compact: String = text.split_whitespace().collect();
+                let bytes = STANDARD
+                    .decode(compact)
+                    .map_err(|e| format!("Invalid base64: {}", e))?;
+                String::from_utf8(bytes).map_err(|_| "Decoded data is not text".to_string())?
+            }
+            Transform::JsonPretty => format_json(text, Some("  "))?,
+            Transform::JsonMinify => format_json(text, None)?,
+            Transform::HtmlEscape => html_escape(text),
+        })
+    }
+}
+
+/// Applies `f` to each line, keeping the original line endings.
+fn map_lines(text: &str, f: impl Fn(&str) -> String) -> String {
+    text.split_inclusive('\n')
+        .map(|line| {
+            let content = line.trim_end_matches(['\r', '\n']);
+            format!("{}{}", f(content), &line[content.len()..])
+        })
+        .collect()
+}
+
+/// Rearranges the lines of `text`, keeping a trailing line break if there was one.
+fn edit_lines(text: &str, edit: impl FnOnce(&mut Vec<&str>)) -> String {
+    let line_ending = if text.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut lines: Vec<&str> = text.lines().collect();
+    edit(&mut lines);
+
+    let mut edited = lines.join(line_ending);
+    if text.ends_with('\n') {
+        edited.push_str(line_ending);
+    }
+    edited
+}
+
+fn title_case(line: &str) -> String {
+    let mut titled = String::with_capacity(line.len());
+    let mut at_word_start = true;
+
+    for c in line.chars() {
+        if at_word_start {
+            titled.extend(c.to_uppercase());
+        } else {
+            titled.extend(c.to_lowercase());
+        }
+        at_word_start = c.is_whitespace();
+    }
+    titled
+}
+
+fn camel_case(line: &str) -> String {
+    words(line)
+        .iter()
+        .enumerate()
+        .map(|(index, word)| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) if index > 0 => first.to_uppercase().chain(chars).collect(),
+                _ => word.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Splits an identifier or phrase into lowercase words, breaking on punctuation,
+/// whitespace and case changes (`parseHTTPResponse` -> parse, http, response).
+fn words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let chars: Vec<char> = line.chars().collect();
+
+    for (index, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && !current.is_empty() {
+            let previous = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_numeric()
+                || (previous.is_uppercase() && next_is_lower)
+            {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.extend(c.to_lowercase());
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Percent-encodes everything except RFC 3986 unreserved characters.
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn url_decode(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let value = text
+                .get(index + 1..index + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| format!("Invalid percent-encoding at position {}", index))?;
+            decoded.push(value);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|_| "Decoded data is not valid UTF-8".to_string())
+}
+
+/// Re-indents JSON with `indent`, or minifies it when `indent` is `None`.
+///
+/// Values are copied as written rather than parsed, so large integers, number
+/// formats like `1e2`, string escapes and key order all survive.
+fn format_json(text: &str, indent: Option<&str>) -> Result<String, String> {
+    serde_json::from_str::<serde::de::IgnoredAny>(text)
+        .map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    let mut formatted = String::with_capacity(text.len());
+    let mut depth = 0;
+    let new_line = |formatted: &mut String, depth: usize| {
+        if let Some(indent) = indent {
+            formatted.push('\n');
+            formatted.push_str(&indent.repeat(depth));
+        }
+    };
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                formatted.push(c);
+                while let Some(c) = chars.next() {
+                    formatted.push(c);
+                    match c {
+                        '\\' => formatted.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '[' => {
+                formatted.push(c);
+                while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+                match chars.next_if(|next| matches!((c, next), ('{', '}') | ('[', ']'))) {
+                    Some(close) => formatted.push(close),
+                    None => {
+                        depth += 1;
+                        new_line(&mut formatted, depth);
+                    }
+                }
+            }
+            '}' | ']' => {
+                depth -= 1;
+                new_line(&mut formatted, depth);
+                formatted.push(c);
+            }
+            ',' => {
+                formatted.push(c);
+                new_line(&mut formatted, depth);
+            }
+            ':' => {
+                formatted.push(c);
+                if indent.is_some() {
+                    formatted.push(' ');
+                }
+            }
+            c if c.is_ascii_whitespace() => {}
+            c => formatted.push(c),
+        }
+    }
+    Ok(formatted)
+}
+
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns the entry's plain text with `transform` applied, e.g. to save as a new entry.
+#[command]
+pub fn transform_entry(entry: ClipboardEntry, transform: Transform) -> Result<String, String> {
+    transform.apply(&entry.plain_text()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transform::*;
+    use super::*;
+
+    fn apply(transform: Transform, text: &str) -> String {
+        transform.apply(text).unwrap()
+    }
+
+    #[test]
+    fn uppercase() {
+        assert_eq!(apply(Uppercase, "Hello, wörld"), "HELLO, WÖRLD");
+    }
+
+    #[test]
+    fn lowercase() {
+        assert_eq!(apply(Lowercase, "Hello, WÖRLD"), "hello, wörld");
+    }
+
+    #[test]
+    fn title_case_capitalizes_each_word_per_line() {
+        assert_eq!(
+            apply(TitleCase, "the QUICK brown\nfox  jumps"),
+            "The Quick Brown\nFox  Jumps"
+        );
+    }
+
+    #[test]
+    fn snake_case_splits_on_case_changes_and_punctuation() {
+        assert_eq!(apply(SnakeCase, "parseHTTPResponse"), "parse_http_response");
+        assert_eq!(apply(SnakeCase, "Hello World-again"), "hello_world_again");
+        assert_eq!(
+            apply(SnakeCase, "userId2Name\nfooBar"),
+            "user_id2_name\nfoo_bar"
+        );
+    }
+
+    #[test]
+    fn camel_case_joins_words() {
+        assert_eq!(apply(CamelCase, "hello world"), "helloWorld");
+        assert_eq!(apply(CamelCase, "snake_case_name"), "snakeCaseName");
+        assert_eq!(apply(CamelCase, "XMLHttpRequest"), "xmlHttpRequest");
+    }
+
+    #[test]
+    fn trim() {
+        assert_eq!(apply(Trim, "  \n padded text\t \n"), "padded text");
+    }
+
+    #[test]
+    fn collapse_whitespace() {
+        assert_eq!(
+            apply(CollapseWhitespace, "  one\t two\n\n three  "),
+            "one two three"
+        );
+    }
+
+    #[test]
+    fn sort_lines_keeps_trailing_newline() {
+        assert_eq!(apply(SortLines, "pear\napple\nfig\n"), "apple\nfig\npear\n");
+        assert_eq!(apply(SortLines, "b\r\na"), "a\r\nb");
+    }
+
+    #[test]
+    fn dedupe_lines_keeps_first_occurrence() {
+        assert_eq!(apply(DedupeLines, "b\na\nb\nc\na"), "b\na\nc");
+    }
+
+    #[test]
+    fn crlf_to_lf() {
+        assert_eq!(apply(CrlfToLf, "one\r\ntwo\r\n"), "one\ntwo\n");
+    }
+
+    #[test]
+    fn lf_to_crlf_does_not_double_existing_crlf() {
+        assert_eq!(apply(LfToCrlf, "one\ntwo\r\nthree"), "one\r\ntwo\r\nthree");
+    }
+
+    #[test]
+    fn url_encode() {
+        assert_eq!(apply(UrlEncode, "a b&c=d/é~"), "a%20b%26c%3Dd%2F%C3%A9~");
+    }
+
+    #[test]
+    fn url_decode() {
+        assert_eq!(apply(UrlDecode, "a%20b%26c%3dd%2F%C3%A9"), "a b&c=d/é");
+        assert!(UrlDecode.apply("100%").is_err());
+        assert!(UrlDecode.apply("%zz").is_err());
+        assert!(UrlDecode.apply("%FF").is_err());
+    }
+
+    #[test]
+    fn base64_encode() {
+        assert_eq!(apply(Base64Encode, "Nabu!"), "TmFidSE=");
+    }
+
+    #[test]
+    fn base64_decode() {
+        assert_eq!(apply(Base64Decode, "TmFi\ndSE=\n"), "Nabu!");
+        assert!(Base64Decode.apply("not base64!").is_err());
+        assert!(Base64Decode.apply("/w==").is_err());
+    }
+
+    #[test]
+    fn json_pretty() {
+        assert_eq!(
+            apply(JsonPretty, r#"{"b":1,"a":[true,null]}"#),
+            "{\n  \"b\": 1,\n  \"a\": [\n    true,\n    null\n  ]\n}"
+        );
+        assert!(JsonPretty.apply("{oops}").is_err());
+    }
+
+    #[test]
+    fn json_minify() {
+        assert_eq!(
+            apply(JsonMinify, "{\n  \"b\": 1,\n  \"a\": \"x y\"\n}"),
+            r#"{"b":1,"a":"x y"}"#
+        );
+        assert!(JsonMinify.apply("[1,").is_err());
+    }
+
+    #[test]
+    fn json_keeps_numbers_and_strings_as_written() {
+        let json =
+            r#"{"id": 123456789012345678901234567890, "n": [1e2, -0.50], "s": "\u00e9 \" ,"}"#;
+        assert_eq!(
+            apply(JsonMinify, json),
+            r#"{"id":123456789012345678901234567890,"n":[1e2,-0.50],"s":"\u00e9 \" ,"}"#
+        );
+        assert_eq!(
+            apply(JsonPretty, json),
+            "{\n  \"id\": 123456789012345678901234567890,\n  \"n\": [\n    1e2,\n    -0.50\n  ],\n  \"s\": \"\\u00e9 \\\" ,\"\n}"
+        );
+    }
+
+    #[test]
+    fn json_keeps_empty_containers_on_one_line() {
+        assert_eq!(
+            apply(JsonPretty, "{ \"a\": [ ], \"b\": { } }"),
+            "{\n  \"a\": [],\n  \"b\": {}\n}"
+        );
+        assert_eq!(apply(JsonMinify, " [ ] "), "[]");
+    }
+
+    #[test]
+    fn html_escape() {
+        assert_eq!(
+            apply(HtmlEscape, r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+}
@@ -1,21 +1,62 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clipboard_rs::common::{RustImage, RustImageData};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
 use tauri::{
-    menu::{Menu, MenuItem},
+    image::Image,
+    menu::{CheckMenuItem, IconMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    App,
+    App, AppHandle, Emitter, Listener, Manager, Wry,
 };
+
+use crate::capture::{capture_status, CaptureStatus};
+use crate::entry::ClipboardEntry;
+use crate::history::History;
+use crate::paste::{write_and_paste, PasteError};
+use crate::settings::SettingsStore;
+
+pub const TRAY_ID: &str = "main";
+
+const TOOLTIP_PREVIEW_CHARS: usize = 40;
+
+/// How many history entries the tray menu lists.
+const RECENT_ENTRIES: u32 = 10;
+const MENU_PREVIEW_CHARS: usize = 48;
+const THUMBNAIL_SIZE: u32 = 32;
+
+/// Menu ids of recent entries are `<prefix><entry id>`.
+const PASTE_ENTRY_PREFIX: &str = "paste_entry:";
+const COPY_ENTRY_PREFIX: &str = "copy_entry:";
+
+/// How long the icon shows a dot after a new entry is recorded.
+const NEW_ENTRY_BADGE: Duration = Duration::from_millis(1500);
+
+const NEW_ENTRY_COLOR: [u8; 3] = [0x22, 0xc5, 0x5e];
+const LOCKED_COLOR: [u8; 3] = [0xf5, 0x9e, 0x0b];
+const ERROR_COLOR: [u8; 3] = [0xef, 0x44, 0x44];
+
+pub struct TrayState {
+    badged: AtomicBool,
+    /// Incremented per new entry, so only the latest badge clears it.
+    badge_generation: AtomicU64,
+}
+
+pub fn init_tray_state() -> TrayState {
+    TrayState {
+        badged: AtomicBool::new(false),
+        badge_generation: AtomicU64::new(0),
+    }
+}
+
 pub fn setup_tray(app: &App) -> Result<(), Box<dyn std::error::Error>> {
-    let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-    let menu = Menu::with_items(app, &[&quit_i])?;
+    let menu = build_menu(app.handle(), &[])?;
 
-    TrayIconBuilder::new()
+    TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Nabu")
         .icon(app.default_window_icon().unwrap().clone())
         .menu(&menu)
         .show_menu_on_left_click(false)
-        .on_menu_event(|app_handle, event| {
-            if event.id() == "quit" {
-                app_handle.exit(0);
-            }
-        })
+        .on_menu_event(|app_handle, event| handle_menu_event(app_handle, event.id().as_ref()))
         .on_tray_icon_event(|tray, event| match event {
             TrayIconEvent::Click {
                 button: MouseButton::Left,
@@ -24,9 +65,321 @@ pub fn setup_tray(app: &App) -> Result<(), Box<dyn std::error::Error>> {
             } => {
                 let _ = crate::visibility::toggle_visibility(&tray.app_handle());
             }
+            // Opening the menu may take focus from the app the user was working
+            // in, so note it while the pointer is still on its way. Linux doesn't
+            // send this; `foreground::track_previous_window` covers it there.
+            TrayIconEvent::Enter { .. } => {
+                crate::foreground::remember_previous_window(tray.app_handle());
+            }
             _ => {}
         })
         .build(app)?;
 
+    // The panel emits this whenever it has added, changed or removed entries.
+    let app_handle = app.handle().clone();
+    app.listen("history-changed", move |_| refresh_menu(&app_handle));
+    refresh_menu(app.handle());
+
     Ok(())
 }
+
+/// Rebuilds the tray menu with the latest history entries.
+pub fn refresh_menu(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+            return;
+        };
+        let entries = app_handle
+            .state::<History>()
+            .recent(RECENT_ENTRIES)
+            .await
+            .unwrap_or_default();
+
+        if let Ok(menu) = build_menu(&app_handle, &entries) {
+            let _ = tray.set_menu(Some(menu));
+        }
+    });
+}
+
+fn build_menu(app_handle: &AppHandle, entries: &[ClipboardEntry]) -> tauri::Result<Menu<Wry>> {
+    let menu = Menu::new(app_handle)?;
+
+    if entries.is_empty() {
+        menu.append(&MenuItem::new(
+            app_handle,
+            "No entries yet",
+            false,
+            None::<&str>,
+        )?)?;
+    } else {
+        // Clicking an entry pastes it; the submenu below copies instead.
+        let copy_menu = Submenu::new(app_handle, "Copy", true)?;
+        for entry in entries {
+            menu.append(&IconMenuItem::with_id(
+                app_handle,
+                format!("{}{}", PASTE_ENTRY_PREFIX, entry.id),
+                entry_label(entry),
+                true,
+                thumbnail(entry),
+                None::<&str>,
+            )?)?;
+            copy_menu.append(&MenuItem::with_id(
+                app_handle,
+                format!("{}{}", COPY_ENTRY_PREFIX, entry.id),
+                entry_label(entry),
+                true,
+                None::<&str>,
+            )?)?;
+        }
+        menu.append(&copy_menu)?;
+    }
+    menu.append(&PredefinedMenuItem::separator(app_handle)?)?;
+
+    let paused = app_handle
+        .state::<crate::capture::CaptureState>()
+        .is_paused();
+    if app_handle
+        .try_state::<crate::typing::TypingState>()
+        .is_some_and(|state| state.is_typing())
+    {
+        menu.append(&MenuItem::with_id(
+            app_handle,
+            "cancel_typing",
+            "Stop Typing",
+            true,
+            None::<&str>,
+        )?)?;
+    }
+    menu.append(&CheckMenuItem::with_id(
+        app_handle,
+        "pause_capture",
+        "Pause Capture",
+        true,
+        paused,
+        None::<&str>,
+    )?)?;
+    menu.append(&MenuItem::with_id(
+        app_handle,
+        "clear_history",
+        "Clear History (Keep Favorites)",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&PredefinedMenuItem::separator(app_handle)?)?;
+    menu.append(&MenuItem::with_id(
+        app_handle,
+        "open",
+        "Open Nabu",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&MenuItem::with_id(
+        app_handle,
+        "settings",
+        "Settings",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&MenuItem::with_id(
+        app_handle,
+        "quit",
+        "Quit",
+        true,
+        None::<&str>,
+    )?)?;
+
+    Ok(menu)
+}
+
+/// The menu label for `entry`: its preview, or "Image" for images.
+fn entry_label(entry: &ClipboardEntry) -> String {
+    let label = match entry.content_type.as_str() {
+        "image" => "Image".to_string(),
+        _ => entry.summary(MENU_PREVIEW_CHARS),
+    };
+    // Menus treat `&` as a mnemonic marker.
+    label.replace('&', "&&")
+}
+
+/// A small preview of an image entry, for menus that can show icons.
+fn thumbnail(entry: &ClipboardEntry) -> Option<Image<'static>> {
+    if entry.content_type != "image" {
+        return None;
+    }
+    let bytes = STANDARD.decode(&entry.content).ok()?;
+    let rgba = RustImageData::from_bytes(&bytes)
+        .and_then(|image| image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE))
+        .and_then(|image| image.to_rgba8())
+        .ok()?;
+    let (width, height) = rgba.dimensions();
+    Some(Image::new_owned(rgba.into_raw(), width, height))
+}
+
+fn handle_menu_event(app_handle: &AppHandle, id: &str) {
+    if let Some(entry_id) = id
+        .strip_prefix(PASTE_ENTRY_PREFIX)
+        .and_then(|entry_id| entry_id.parse().ok())
+    {
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let _ = paste_entry(&app_handle, entry_id).await;
+        });
+        return;
+    }
+    if let Some(entry_id) = id
+        .strip_prefix(COPY_ENTRY_PREFIX)
+        .and_then(|entry_id| entry_id.parse().ok())
+    {
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let _ = copy_entry(&app_handle, entry_id).await;
+        });
+        return;
+    }
+
+    match id {
+        "pause_capture" => {
+            let paused = app_handle
+                .state::<crate::capture::CaptureState>()
+                .is_paused();
+            crate::capture::set_paused(app_handle, !paused);
+        }
+        "cancel_typing" => {
+            if let Some(state) = app_handle.try_state::<crate::typing::TypingState>() {
+                state.cancel();
+            }
+        }
+        "clear_history" => {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                if app_handle.state::<History>().clear(true).await.is_ok() {
+                    let _ = app_handle.emit("history-cleared", ());
+                    refresh_menu(&app_handle);
+                }
+            });
+        }
+        "open" => {
+            let _ = crate::visibility::show_panel(app_handle);
+        }
+        "settings" => {
+            let _ = crate::settings_window::open_settings_window(app_handle);
+        }
+        "quit" => app_handle.exit(0),
+        _ => {}
+    }
+}
+
+async fn load_entry(app_handle: &AppHandle, entry_id: i64) -> Result<ClipboardEntry, String> {
+    let mut entries = app_handle.state::<History>().entries(&[entry_id]).await?;
+    Ok(entries.remove(0))
+}
+
+async fn copy_entry(app_handle: &AppHandle, entry_id: i64) -> Result<(), String> {
+    load_entry(app_handle, entry_id).await?.write_to_clipboard()
+}
+
+/// Pastes the entry into the app that was focused before the tray menu opened.
+async fn paste_entry(app_handle: &AppHandle, entry_id: i64) -> Result<(), PasteError> {
+    let entry = load_entry(app_handle, entry_id).await?;
+    let restore_clipboard = app_handle.state::<SettingsStore>().get().restore_clipboard;
+
+    write_and_paste(app_handle, restore_clipboard, || entry.write_to_clipboard()).await
+}
+
+/// Shows the capture status and the paste queue, if any, in the tray tooltip.
+pub fn update_tooltip(app_handle: &AppHandle) {
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    let mut tooltip = match capture_status(app_handle) {
+        CaptureStatus::Recording => "Nabu".to_string(),
+        CaptureStatus::Paused => "Nabu - paused".to_string(),
+        CaptureStatus::Locked => "Nabu - holding the clipboard for a paste".to_string(),
+        CaptureStatus::Error(error) => format!("Nabu - not recording: {}", error),
+    };
+
+    let queue = app_handle.state::<crate::paste_queue::PasteQueue>();
+    if let Some(next) = queue.next() {
+        tooltip.push_str(&format!(
+            " - {} queued, next: {}",
+            queue.entries().len(),
+            next.summary(TOOLTIP_PREVIEW_CHARS)
+        ));
+    }
+
+    let _ = tray.set_tooltip(Some(tooltip));
+}
+
+/// Updates the tray icon and tooltip to the current capture status.
+pub fn update_status(app_handle: &AppHandle) {
+    let (Some(tray), Some(base)) = (
+        app_handle.tray_by_id(TRAY_ID),
+        app_handle.default_window_icon(),
+    ) else {
+        return;
+    };
+
+    let badged = app_handle
+        .state::<TrayState>()
+        .badged
+        .load(Ordering::SeqCst);
+    let _ = tray.set_icon(Some(status_icon(base, &capture_status(app_handle), badged)));
+    update_tooltip(app_handle);
+}
+
+/// Briefly marks the tray icon after a new entry is recorded.
+pub fn flash_new_entry(app_handle: &AppHandle) {
+    let state = app_handle.state::<TrayState>();
+    let generation = state.badge_generation.fetch_add(1, Ordering::SeqCst) + 1;
+    state.badged.store(true, Ordering::SeqCst);
+    update_status(app_handle);
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(NEW_ENTRY_BADGE).await;
+        let state = app_handle.state::<TrayState>();
+        if state.badge_generation.load(Ordering::SeqCst) == generation {
+            state.badged.store(false, Ordering::SeqCst);
+            update_status(&app_handle);
+        }
+    });
+}
+
+/// The app icon, greyed out while paused and with a coloured dot in the corner
+/// when locked, failing or showing a new entry.
+fn status_icon(base: &Image<'_>, status: &CaptureStatus, badged: bool) -> Image<'static> {
+    let (width, height) = (base.width(), base.height());
+    let mut rgba = base.rgba().to_vec();
+
+    if *status == CaptureStatus::Paused {
+        for pixel in rgba.chunks_exact_mut(4) {
+            let luma = (pixel[0] as u32 * 30 + pixel[1] as u32 * 59 + pixel[2] as u32 * 11) / 100;
+            pixel[..3].fill(luma as u8);
+            pixel[3] /= 2;
+        }
+    }
+
+    let dot = match status {
+        CaptureStatus::Error(_) => Some(ERROR_COLOR),
+        CaptureStatus::Locked => Some(LOCKED_COLOR),
+        CaptureStatus::Recording if badged => Some(NEW_ENTRY_COLOR),
+        _ => None,
+    };
+    if let Some(color) = dot {
+        let radius = width.min(height) as i64 / 4;
+        let (cx, cy) = (width as i64 - radius, height as i64 - radius);
+        for y in 0..height as i64 {
+            for x in 0..width as i64 {
+                if (x - cx).pow(2) + (y - cy).pow(2) <= radius.pow(2) {
+                    let i = ((y * width as i64 + x) * 4) as usize;
+                    rgba[i..i + 3].copy_from_slice(&color);
+                    rgba[i + 3] = 0xff;
+                }
+            }
+        }
+    }
+
+    Image::new_owned(rgba, width, height)
+}
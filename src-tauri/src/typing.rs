@@ -0,0 +1,168 @@
+use enigo::{Direction, Key, Keyboard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{command, AppHandle, Manager, State};
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Shortcut};
+use tokio::time::sleep;
+
+use crate::entry::ClipboardEntry;
+use crate::paste::{with_enigo, PasteError};
+use crate::settings::SettingsStore;
+
+/// Characters sent per input call when no delay is configured, so Escape is
+/// still noticed while typing long entries.
+const UNDELAYED_CHUNK_SIZE: usize = 64;
+
+pub struct TypingState {
+    typing: AtomicBool,
+    cancelled: AtomicBool,
+}
+
+impl TypingState {
+    pub fn is_typing(&self) -> bool {
+        self.typing.load(Ordering::SeqCst)
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+}
+
+pub fn init_typing_state() -> TypingState {
+    TypingState {
+        typing: AtomicBool::new(false),
+        cancelled: AtomicBool::new(false),
+    }
+}
+
+enum Segment {
+    Text(String),
+    Key(Key),
+}
+
+/// Splits `text` so newlines and tabs are sent as key presses rather than characters.
+fn segments(text: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let key = match c {
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                Key::Return
+            }
+            '\n' => Key::Return,
+            '\t' => Key::Tab,
+            _ => {
+                current.push(c);
+                continue;
+            }
+        };
+
+        if !current.is_empty() {
+            segments.push(Segment::Text(std::mem::take(&mut current)));
+        }
+        segments.push(Segment::Key(key));
+    }
+
+    if !current.is_empty() {
+        segments.push(Segment::Text(current));
+    }
+
+    segments
+}
+
+/// The key that stops typing early.
+pub fn escape_shortcut() -> Shortcut {
+    Shortcut::new(None, Code::Escape)
+}
+
+/// Types `text` into the focused application. Pressing Escape stops it early.
+pub async fn type_text(app: &AppHandle, text: &str) -> Result<(), String> {
+    let state = app.state::<TypingState>();
+    if state.typing.swap(true, Ordering::SeqCst) {
+        return Err("Already typing another entry".into());
+    }
+    state.cancelled.store(false, Ordering::SeqCst);
+    // Offers "Stop Typing" in the tray while this runs.
+    crate::tray::refresh_menu(app);
+
+    // Escape may already be bound as a global shortcut; the shortcut handler
+    // cancels typing before running its action, so only register it if not.
+    let escape = escape_shortcut();
+    let owns_escape = !app.global_shortcut().is_registered(escape);
+    if owns_escape {
+        if let Err(e) = app.global_shortcut().register(escape) {
+            state.typing.store(false, Ordering::SeqCst);
+            crate::tray::refresh_menu(app);
+            return Err(format!("Could not listen for Escape to stop typing: {}", e));
+        }
+    }
+
+    let result = type_segments(app, &state, text).await;
+
+    if owns_escape {
+        let _ = app.global_shortcut().unregister(escape);
+    }
+    state.typing.store(false, Ordering::SeqCst);
+    crate::tray::refresh_menu(app);
+
+    result
+}
+
+async fn type_segments(app: &AppHandle, state: &TypingState, text: &str) -> Result<(), String> {
+    let delay_ms = app.state::<SettingsStore>().get().typing_delay_ms;
+    let delay = Duration::from_millis(delay_ms);
+    let chunk_size = if delay.is_zero() {
+        UNDELAYED_CHUNK_SIZE
+    } else {
+        1
+    };
+
+    for segment in segments(text) {
+        let chunks = match segment {
+            Segment::Key(key) => vec![Segment::Key(key)],
+            Segment::Text(text) => text
+                .chars()
+                .collect::<Vec<_>>()
+                .chunks(chunk_size)
+                .map(|chunk| Segment::Text(chunk.iter().collect()))
+                .collect(),
+        };
+
+        for chunk in chunks {
+            if state.cancelled.load(Ordering::SeqCst) {
+                return Err("Typing was cancelled".into());
+            }
+
+            with_enigo(app, move |enigo| {
+                match chunk {
+                    Segment::Text(text) => enigo.text(&text),
+                    Segment::Key(key) => enigo.key(key, Direction::Click),
+                }
+                .map_err(|e| e.to_string())
+            })
+            .await?;
+
+            if !delay.is_zero() {
+                sleep(delay).await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Types `entry` out as plain text, at the speed set by `typingDelayMs`.
+#[command]
+pub async fn paste_typed(app: AppHandle, entry: ClipboardEntry) -> Result<(), PasteError> {
+    let text = entry.plain_text()?;
+    crate::paste::restore_focus(&app).await?;
+    type_text(&app, &text).await.map_err(PasteError::from)
+}
+
+#[command]
+pub fn cancel_typing(state: State<TypingState>) {
+    state.cancel();
+}
@@ -1,9 +1,75 @@
-use tauri::AppHandle;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
 
+use crate::settings::SettingsStore;
+
+/// Focus changes this soon after showing the panel are the window manager
+/// settling, not the user clicking elsewhere.
+const SHOW_GRACE: Duration = Duration::from_millis(500);
+
+/// How long the panel may be unfocused before it is hidden. Covers the brief
+/// focus loss some window managers cause while dragging or opening dialogs.
+#[cfg(not(target_os = "macos"))]
+const BLUR_GRACE: Duration = Duration::from_millis(250);
+
+pub struct VisibilityState {
+    shown_at: Mutex<Option<Instant>>,
+}
+
+pub fn init_visibility_state() -> VisibilityState {
+    VisibilityState {
+        shown_at: Mutex::new(None),
+    }
+}
+
+/// Whether losing focus right now should hide the panel.
+pub fn should_hide_on_blur(app_handle: &AppHandle) -> bool {
+    let state = app_handle.state::<VisibilityState>();
+    let settled = state
+        .shown_at
+        .lock()
+        .unwrap()
+        .is_none_or(|shown_at| shown_at.elapsed() >= SHOW_GRACE);
+
+    app_handle.state::<SettingsStore>().get().hide_on_blur && settled
+}
+
+/// Hides the panel when it loses focus. macOS does this in `panel::setup_panel`.
 #[cfg(not(target_os = "macos"))]
-use tauri::Manager;
+pub fn setup_hide_on_blur(app_handle: &AppHandle) {
+    let Some(window) = app_handle.get_webview_window("main") else {
+        return;
+    };
+
+    let app_handle = app_handle.clone();
+    window.on_window_event(move |event| {
+        if !matches!(event, tauri::WindowEvent::Focused(false)) {
+            return;
+        }
+
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(BLUR_GRACE).await;
+
+            let refocused = app_handle
+                .get_webview_window("main")
+                .and_then(|window| window.is_focused().ok())
+                .unwrap_or(false);
+            if !refocused && should_hide_on_blur(&app_handle) {
+                let app_clone = app_handle.clone();
+                let _ = app_handle.run_on_main_thread(move || {
+                    let _ = hide_panel(&app_clone);
+                });
+            }
+        });
+    });
+}
 
 pub fn hide_panel(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    crate::placement::remember_position(app_handle);
+    crate::window_geometry::save_geometry(app_handle);
+
     #[cfg(target_os = "macos")]
     {
         use tauri_nspanel::ManagerExt;
@@ -23,7 +89,36 @@ pub fn hide_panel(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Erro
     Ok(())
 }
 
+pub fn is_panel_visible(app_handle: &AppHandle) -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        use tauri_nspanel::ManagerExt;
+        app_handle
+            .get_webview_panel("main")
+            .map(|panel| panel.is_visible())
+            .unwrap_or(false)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        app_handle
+            .get_webview_window("main")
+            .and_then(|window| window.is_visible().ok())
+            .unwrap_or(false)
+    }
+}
+
 pub fn show_panel(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    crate::foreground::remember_previous_window(app_handle);
+    *app_handle
+        .state::<VisibilityState>()
+        .shown_at
+        .lock()
+        .unwrap() = Some(Instant::now());
+    // Better shown where it was than not at all, e.g. on Wayland where the
+    // cursor position is unavailable.
+    let _ = crate::placement::position_panel(app_handle);
+
     #[cfg(target_os = "macos")]
     {
         use tauri_nspanel::ManagerExt;
@@ -80,4 +175,4 @@ pub fn toggle_visibility(app_handle: &AppHandle) -> Result<bool, Box<dyn std::er
             Ok(false)
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, LogicalSize, Manager, Monitor, PhysicalPosition, WebviewWindow};
+
+use crate::placement::clamp_to_work_area;
+
+const GEOMETRY_FILE: &str = "window_state.json";
+
+/// The panel's size and position, saved when it is hidden and when the app quits.
+#[derive(Debug, Serialize, Deserialize)]
+struct WindowGeometry {
+    /// The name of the monitor the panel was on.
+    monitor: Option<String>,
+    /// Physical pixels, in the virtual screen's coordinates.
+    x: i32,
+    y: i32,
+    /// Logical pixels, so the size survives moving between monitors with
+    /// different scale factors.
+    width: f64,
+    height: f64,
+}
+
+fn geometry_path(app_handle: &AppHandle) -> Option<PathBuf> {
+    app_handle
+        .path()
+        .app_config_dir()
+        .ok()
+        .map(|dir| dir.join(GEOMETRY_FILE))
+}
+
+fn read_geometry(window: &WebviewWindow) -> tauri::Result<WindowGeometry> {
+    let position = window.outer_position()?;
+    let size = window
+        .inner_size()?
+        .to_logical::<f64>(window.scale_factor()?);
+    let monitor = window
+        .current_monitor()?
+        .and_then(|monitor| monitor.name().cloned());
+
+    Ok(WindowGeometry {
+        monitor,
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Saves the panel's geometry. Hidden windows report stale or zero positions on
+/// some platforms, so only a visible panel is saved.
+pub fn save_geometry(app_handle: &AppHandle) {
+    let Some(window) = app_handle.get_webview_window("main") else {
+        return;
+    };
+    if !window.is_visible().unwrap_or(false) {
+        return;
+    }
+    let (Ok(geometry), Some(path)) = (read_geometry(&window), geometry_path(app_handle)) else {
+        return;
+    };
+
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&geometry) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+/// Restores the geometry saved by [`save_geometry`]. If the saved monitor is
+/// gone, the panel is moved onto the primary monitor instead.
+pub fn restore_geometry(app_handle: &AppHandle) -> tauri::Result<()> {
+    let Some(window) = app_handle.get_webview_window("main") else {
+        return Ok(());
+    };
+    let Some(geometry) = geometry_path(app_handle)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str::<WindowGeometry>(&json).ok())
+    else {
+        return Ok(());
+    };
+
+    let saved_monitor = geometry.monitor.as_ref().and_then(|name| {
+        app_handle
+            .available_monitors()
+            .ok()?
+            .into_iter()
+            .find(|monitor| monitor.name() == Some(name))
+    });
+    let Some(monitor) = saved_monitor.or(app_handle.primary_monitor()?) else {
+        return Ok(());
+    };
+
+    let size = fit_to_work_area(&monitor, geometry.width, geometry.height);
+    window.set_size(size)?;
+
+    let position = clamp_to_work_area(
+        &monitor,
+        PhysicalPosition::new(geometry.x, geometry.y),
+        size.to_physical(monitor.scale_factor()),
+    );
+    window.set_position(position)
+}
+
+/// Shrinks the saved size if the monitor's work area is now smaller than it.
+fn fit_to_work_area(monitor: &Monitor, width: f64, height: f64) -> LogicalSize<f64> {
+    let area = monitor
+        .work_area()
+        .size
+        .to_logical::<f64>(monitor.scale_factor());
+    LogicalSize::new(width.min(area.width), height.min(area.height))
+}
@@ -6,16 +6,29 @@ import { ClipboardProvider } from '@/clipboard-context';
 import AppLayout from '@/AppLayout';
 import { TooltipProvider } from '@/components/ui/tooltip';
 import { KeyboardProvider } from '@/context/keyboard-context.tsx';
+import { isPinnedNoteWindow, PinnedNote } from '@/components/pinned-note';
+import SettingsPage, { isSettingsWindow } from '@/components/settings/settings-page';
 
 function App() {
   const queryClient = new QueryClient();
+
+  // Pinned notes share the bundle but not the panel, which would start a second
+  // clipboard monitor.
+  if (isPinnedNoteWindow()) {
+    return (
+      <ThemeProvider defaultMode='dark'>
+        <PinnedNote />
+      </ThemeProvider>
+    );
+  }
+
   return (
     <ThemeProvider defaultMode='dark'>
       <ClipboardProvider>
         <KeyboardProvider>
           <QueryClientProvider client={queryClient}>
             <TooltipProvider>
-              <AppLayout />
+              {isSettingsWindow() ? <SettingsPage /> : <AppLayout />}
             </TooltipProvider>
           </QueryClientProvider>
         </KeyboardProvider>
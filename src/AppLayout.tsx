@@ -1,7 +1,6 @@
 import { Route, Routes, useNavigate } from 'react-router';
 
 import Clipboard from '@/components/clipboard/clipboard';
-import SettingsPage from '@/components/settings/settings-page';
 import { TitleBar } from '@/components/title-bar';
 import useClipboardInit from '@/hooks/use-clipboard-init';
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
@@ -33,7 +32,6 @@ export default function AppLayout() {
       <main className='flex-1 overflow-hidden'>
         <Routes>
           <Route path='/' element={<Clipboard />} />
-          <Route path='/settings' element={<SettingsPage />} />
         </Routes>
       </main>
     </div>
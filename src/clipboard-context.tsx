@@ -1,7 +1,6 @@
 import React, { createContext, ReactNode, useContext, useMemo, useReducer } from 'react';
 
 import { ClipboardContentType, ClipboardEntry, SortBy, SortDirection } from '@/types/clipboard';
-import { DEFAULT_SHORTCUTS, type ShortcutDefinition } from '@/types/shortcuts';
 
 interface ClipboardState {
   searchQuery: string;
@@ -10,7 +9,7 @@ interface ClipboardState {
   sortDirection: SortDirection;
   showFavoritesOnly: boolean;
   selectedClipboardEntry: ClipboardEntry | null;
-  currentShortcut: ShortcutDefinition;
+  checkedEntryIds: number[];
 }
 
 type ClipboardAction =
@@ -20,7 +19,8 @@ type ClipboardAction =
   | { type: 'SET_SEARCH_SORT'; payload: { sortBy: SortBy; sortDirection: SortDirection } }
   | { type: 'TOGGLE_FAVORITES_ONLY' }
   | { type: 'SELECT_CLIPBOARD_ENTRY'; payload: ClipboardEntry | null }
-  | { type: 'SET_SHORTCUT'; payload: ShortcutDefinition };
+  | { type: 'TOGGLE_CHECKED_ENTRY'; payload: number }
+  | { type: 'CLEAR_CHECKED_ENTRIES' };
 
 const initialState: ClipboardState = {
   searchQuery: '',
@@ -29,7 +29,7 @@ const initialState: ClipboardState = {
   sortDirection: 'DESC',
   showFavoritesOnly: false,
   selectedClipboardEntry: null,
-  currentShortcut: DEFAULT_SHORTCUTS.launch,
+  checkedEntryIds: [],
 };
 
 function clipboardReducer(state: ClipboardState, action: ClipboardAction): ClipboardState {
@@ -56,8 +56,16 @@ function clipboardReducer(state: ClipboardState, action: ClipboardAction): Clipb
       return { ...state, showFavoritesOnly: !state.showFavoritesOnly };
     case 'SELECT_CLIPBOARD_ENTRY':
       return { ...state, selectedClipboardEntry: action.payload };
-    case 'SET_SHORTCUT':
-      return { ...state, currentShortcut: action.payload };
+    case 'TOGGLE_CHECKED_ENTRY': {
+      // Kept in click order, which is the order entries are joined in.
+      const id = action.payload;
+      const checkedEntryIds = state.checkedEntryIds.includes(id)
+        ? state.checkedEntryIds.filter((checkedId) => checkedId !== id)
+        : [...state.checkedEntryIds, id];
+      return { ...state, checkedEntryIds };
+    }
+    case 'CLEAR_CHECKED_ENTRIES':
+      return { ...state, checkedEntryIds: [] };
     default:
       return state;
   }
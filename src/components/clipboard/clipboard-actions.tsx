@@ -1,21 +1,20 @@
-import { ClipboardPaste, Copy } from 'lucide-react';
+import { ClipboardPaste, Copy, ListX } from 'lucide-react';
 
 import { cn } from '@/lib/utils';
 import { useClipboardActions } from '@/hooks/use-clipboard-actions';
 import { useClipboardContext } from '@/clipboard-context';
 import { TooltipButton } from '@/components/ui/tooltip-button';
-import { useSetting } from '@/hooks/use-setting';
-import { DEFAULT_KEYBOARD_NAVIGATION, SETTING_KEYS } from '@/types/settings';
+import MacroDropdown from '@/components/clipboard/macro-dropdown';
+import { useKeyboardNavigation } from '@/hooks/use-keyboard-navigation';
+import { usePasteQueue } from '@/hooks/use-paste-queue';
 import { formatShortcut } from '@/util/clipboard-parser';
 
 const ClipboardActions = () => {
   const { copyEntry, pasteEntry } = useClipboardActions();
   const { state } = useClipboardContext();
+  const { queue, clearQueue } = usePasteQueue();
 
-  const { value: navSettings } = useSetting(
-    SETTING_KEYS.KEYBOARD_NAVIGATION,
-    DEFAULT_KEYBOARD_NAVIGATION
-  );
+  const { value: navSettings } = useKeyboardNavigation();
 
   const pasteKeybind = formatShortcut(
     navSettings.shortcuts.pasteEntry.modifiers,
@@ -28,6 +27,22 @@ const ClipboardActions = () => {
 
   return (
     <div className='flex items-center gap-2'>
+      {queue.length > 0 && (
+        <TooltipButton
+          tooltipContent={`Clear paste queue (next: ${queue[0].preview ?? ''})`}
+          tooltipSide='bottom'
+          variant='ghost'
+          size='sm'
+          onClick={clearQueue}
+          className='h-8 px-2 gap-1 text-xs transition-colors'
+        >
+          <ListX className='h-4 w-4' />
+          {queue.length}
+        </TooltipButton>
+      )}
+
+      <MacroDropdown />
+
       <TooltipButton
         tooltipContent='Paste'
         kbd={pasteKeybind.join(' + ')}
@@ -1,26 +1,85 @@
 import React from 'react';
-import { Copy, Star, Trash } from 'lucide-react';
+import {
+  ClipboardPaste,
+  Copy,
+  FilePen,
+  FilePlus,
+  Keyboard,
+  ListPlus,
+  Pin,
+  Save,
+  Star,
+  Trash,
+  WandSparkles,
+} from 'lucide-react';
 
 import {
   ContextMenu,
   ContextMenuContent,
   ContextMenuItem,
+  ContextMenuSeparator,
+  ContextMenuSub,
+  ContextMenuSubContent,
+  ContextMenuSubTrigger,
   ContextMenuTrigger,
 } from '@/components/ui/context-menu';
 import { ClipboardEntry } from '@/types/clipboard';
 import { cn } from '@/lib/utils';
 import { useClipboardActions } from '@/hooks/use-clipboard-actions';
 import { useClipboardContext } from '@/clipboard-context';
+import { usePasteQueue } from '@/hooks/use-paste-queue';
+import { useScriptTransforms } from '@/hooks/use-script-transforms';
 import { ClipboardEntryIcon } from '@/util/clipboard-content-icons';
+import { TRANSFORMS } from '@/types/transforms';
+import { useAppSetting } from '@/hooks/use-app-setting';
+import { useConfigFile } from '@/hooks/use-config-file';
 
 interface ClipboardDetailProps {
   entry: ClipboardEntry;
 }
 
 const ClipboardDetail: React.FC<ClipboardDetailProps> = ({ entry }) => {
-  const { copyEntry, deleteEntry, selectOrCopyEntry, toggleEntryFavorite } = useClipboardActions();
+  const {
+    copyEntry,
+    deleteEntry,
+    selectOrCopyEntry,
+    toggleEntryChecked,
+    pasteCheckedEntries,
+    copyCheckedEntries,
+    saveAsSnippet,
+    typeOutEntry,
+    pasteTransformedEntry,
+    saveTransformedEntry,
+    pasteScriptTransformedEntry,
+    saveScriptTransformedEntry,
+    pasteCommandOutput,
+    saveCommandOutput,
+    editEntryExternally,
+    pinEntry,
+    toggleEntryFavorite,
+  } = useClipboardActions();
+  const { scripts } = useScriptTransforms();
+  const { value: settingsCommands } = useAppSetting('externalCommands');
+  const { transforms: configCommands } = useConfigFile();
+  // The backend runs commands by name, and nabu.toml's win over the settings'.
+  const externalCommands = [
+    ...configCommands,
+    ...settingsCommands.filter((command) => !configCommands.some((t) => t.name === command.name)),
+  ];
   const { state } = useClipboardContext();
+  const { enqueueEntries } = usePasteQueue();
   const isSelected = state.selectedClipboardEntry?.id === entry.id;
+  const isChecked = state.checkedEntryIds.includes(entry.id);
+  const checkedCount = state.checkedEntryIds.length;
+  const hasPlainText = !['image', 'file'].includes(entry.contentType);
+
+  const handleClick = (event: React.MouseEvent) => {
+    if (event.ctrlKey || event.metaKey) {
+      toggleEntryChecked(entry.id);
+    } else {
+      selectOrCopyEntry(entry);
+    }
+  };
 
   return (
     <ContextMenu>
@@ -29,9 +88,10 @@ const ClipboardDetail: React.FC<ClipboardDetailProps> = ({ entry }) => {
           data-entry-id={entry.id}
           className={cn(
             'flex items-center gap-3 px-4 py-3 cursor-pointer border-b border-border hover:bg-accent transition-colors h-full',
-            isSelected && 'bg-accent'
+            isSelected && 'bg-accent',
+            isChecked && 'bg-primary/10'
           )}
-          onClick={() => selectOrCopyEntry(entry)}
+          onClick={handleClick}
           title={isSelected ? 'Click again to copy' : 'Click to preview'}
         >
           <div className='flex-shrink-0 w-6 h-6 flex items-center justify-center'>
@@ -51,9 +111,111 @@ const ClipboardDetail: React.FC<ClipboardDetailProps> = ({ entry }) => {
         </div>
       </ContextMenuTrigger>
       <ContextMenuContent>
+        {checkedCount > 1 && (
+          <>
+            <ContextMenuItem className='flex items-center' onClick={pasteCheckedEntries}>
+              <ClipboardPaste className='h-4 w-4 mr-2' /> Paste {checkedCount} Selected
+            </ContextMenuItem>
+            <ContextMenuItem className='flex items-center' onClick={copyCheckedEntries}>
+              <Copy className='h-4 w-4 mr-2' /> Copy {checkedCount} Selected
+            </ContextMenuItem>
+            <ContextMenuSeparator />
+          </>
+        )}
         <ContextMenuItem className='flex items-center' onClick={() => copyEntry(entry)}>
           <Copy className='h-4 w-4 mr-2' /> Copy to Clipboard
         </ContextMenuItem>
+        <ContextMenuItem className='flex items-center' onClick={() => enqueueEntries([entry])}>
+          <ListPlus className='h-4 w-4 mr-2' /> Add to Paste Queue
+        </ContextMenuItem>
+        {hasPlainText && (
+          <>
+            <ContextMenuItem
+              className='flex items-center'
+              title='Press Esc to stop'
+              onClick={() => typeOutEntry(entry)}
+            >
+              <Keyboard className='h-4 w-4 mr-2' /> Type Out
+            </ContextMenuItem>
+            <ContextMenuSub>
+              <ContextMenuSubTrigger className='flex items-center'>
+                <WandSparkles className='h-4 w-4 mr-2' /> Paste Transformed
+              </ContextMenuSubTrigger>
+              <ContextMenuSubContent>
+                {TRANSFORMS.map(({ value, label }) => (
+                  <ContextMenuItem key={value} onClick={() => pasteTransformedEntry(entry, value)}>
+                    {label}
+                  </ContextMenuItem>
+                ))}
+                {scripts.length > 0 && <ContextMenuSeparator />}
+                {scripts.map((script) => (
+                  <ContextMenuItem
+                    key={script.name}
+                    title={script.description}
+                    onClick={() => pasteScriptTransformedEntry(entry, script.name)}
+                  >
+                    {script.name}
+                  </ContextMenuItem>
+                ))}
+                {externalCommands.length > 0 && <ContextMenuSeparator />}
+                {externalCommands.map((command) => (
+                  <ContextMenuItem
+                    key={command.name}
+                    title={command.commandLine}
+                    onClick={() => pasteCommandOutput(entry, command)}
+                  >
+                    {command.name}
+                  </ContextMenuItem>
+                ))}
+              </ContextMenuSubContent>
+            </ContextMenuSub>
+            <ContextMenuSub>
+              <ContextMenuSubTrigger className='flex items-center'>
+                <Save className='h-4 w-4 mr-2' /> Save Transformed as New Entry
+              </ContextMenuSubTrigger>
+              <ContextMenuSubContent>
+                {TRANSFORMS.map(({ value, label }) => (
+                  <ContextMenuItem key={value} onClick={() => saveTransformedEntry(entry, value)}>
+                    {label}
+                  </ContextMenuItem>
+                ))}
+                {scripts.length > 0 && <ContextMenuSeparator />}
+                {scripts.map((script) => (
+                  <ContextMenuItem
+                    key={script.name}
+                    title={script.description}
+                    onClick={() => saveScriptTransformedEntry(entry, script.name)}
+                  >
+                    {script.name}
+                  </ContextMenuItem>
+                ))}
+                {externalCommands.length > 0 && <ContextMenuSeparator />}
+                {externalCommands.map((command) => (
+                  <ContextMenuItem
+                    key={command.name}
+                    title={command.commandLine}
+                    onClick={() => saveCommandOutput(entry, command)}
+                  >
+                    {command.name}
+                  </ContextMenuItem>
+                ))}
+              </ContextMenuSubContent>
+            </ContextMenuSub>
+          </>
+        )}
+        {entry.contentType !== 'image' && (
+          <ContextMenuItem className='flex items-center' onClick={() => editEntryExternally(entry)}>
+            <FilePen className='h-4 w-4 mr-2' /> Edit in External Editor
+          </ContextMenuItem>
+        )}
+        <ContextMenuItem className='flex items-center' onClick={() => pinEntry(entry)}>
+          <Pin className='h-4 w-4 mr-2' /> Pin as Note
+        </ContextMenuItem>
+        {!['image', 'file', 'html', 'rtf'].includes(entry.contentType) && (
+          <ContextMenuItem className='flex items-center' onClick={() => saveAsSnippet(entry)}>
+            <FilePlus className='h-4 w-4 mr-2' /> Save as Snippet
+          </ContextMenuItem>
+        )}
         <ContextMenuItem
           className='flex items-center'
           onClick={(e) => toggleEntryFavorite(entry.id, e)}
@@ -1,6 +1,7 @@
 import React from 'react';
 
 import ClipboardList from '@/components/clipboard/clipboard-list';
+import SnippetList from '@/components/clipboard/snippet-list';
 import { ResizableHandle, ResizablePanel, ResizablePanelGroup } from '@/components/ui/resizable';
 import ClipboardPreview from '@/components/clipboard/preview-panel';
 import ClipboardSearchBar from '@/components/clipboard/search/clipboard-searchbar';
@@ -34,6 +35,7 @@ const Clipboard: React.FC = () => {
             toggleFilterSidebar={toggleFilterSidebar}
             isFilterSidebarCollapsed={layout.isFilterSidebarCollapsed}
           />
+          <SnippetList />
           <ClipboardList />
         </ResizablePanel>
 
@@ -0,0 +1,43 @@
+import { ListVideo } from 'lucide-react';
+
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuLabel,
+  DropdownMenuSeparator,
+  DropdownMenuTrigger,
+} from '@/components/ui/dropdown-menu';
+import { TooltipButton } from '@/components/ui/tooltip-button';
+import { usePasteMacros } from '@/hooks/use-paste-macros';
+
+export default function MacroDropdown() {
+  const { macros, runMacro } = usePasteMacros();
+
+  if (macros.length === 0) return null;
+
+  return (
+    <DropdownMenu>
+      <DropdownMenuTrigger asChild>
+        <TooltipButton
+          variant='ghost'
+          size='sm'
+          className='h-8 w-8 p-0 transition-colors'
+          tooltipContent='Run macro'
+          tooltipSide='bottom'
+        >
+          <ListVideo className='h-4 w-4' />
+        </TooltipButton>
+      </DropdownMenuTrigger>
+      <DropdownMenuContent className='w-56'>
+        <DropdownMenuLabel>Macros</DropdownMenuLabel>
+        <DropdownMenuSeparator />
+        {macros.map((pasteMacro) => (
+          <DropdownMenuItem key={pasteMacro.id} onSelect={() => runMacro(pasteMacro.id)}>
+            {pasteMacro.name}
+          </DropdownMenuItem>
+        ))}
+      </DropdownMenuContent>
+    </DropdownMenu>
+  );
+}
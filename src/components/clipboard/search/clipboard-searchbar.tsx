@@ -6,14 +6,9 @@ import { useClipboardContext } from '@/clipboard-context';
 import { TooltipButton } from '@/components/ui/tooltip-button';
 import { useClipboardActions } from '@/hooks/use-clipboard-actions';
 import { useKeyboardShortcut } from '@/context/keyboard-context';
-import { useSetting } from '@/hooks/use-setting';
+import { useKeyboardNavigation } from '@/hooks/use-keyboard-navigation';
 import { cn } from '@/lib/utils';
 import SortDropdown from '@/components/clipboard/search/sort-dropdown';
-import {
-  DEFAULT_KEYBOARD_NAVIGATION,
-  KeyboardNavigationSettings,
-  SETTING_KEYS,
-} from '@/types/settings';
 
 const ClipboardSearchBar = ({
   toggleFilterSidebar,
@@ -27,10 +22,7 @@ const ClipboardSearchBar = ({
   const { state, dispatch } = useClipboardContext();
   const { searchQuery, showFavoritesOnly, searchFilters } = state;
 
-  const { value: settings } = useSetting<KeyboardNavigationSettings>(
-    SETTING_KEYS.KEYBOARD_NAVIGATION,
-    DEFAULT_KEYBOARD_NAVIGATION
-  );
+  const { value: settings } = useKeyboardNavigation();
 
   const placeholderText = useMemo(() => {
     if (!searchFilters || searchFilters.length === 0) {
@@ -0,0 +1,90 @@
+import { FormEvent, useEffect, useState } from 'react';
+
+import { Button } from '@/components/ui/button';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import useDebounce from '@/hooks/use-debounce';
+import clipboardService from '@/lib/clipboard-service';
+import { cn, scrollbarStyles } from '@/lib/utils';
+import { Snippet } from '@/types/snippets';
+
+interface SnippetInputDialogProps {
+  snippet: Snippet | null;
+  fields: string[];
+  onSubmit: (inputs: Record<string, string>) => void;
+  onClose: () => void;
+}
+
+export function SnippetInputDialog({ snippet, fields, onSubmit, onClose }: SnippetInputDialogProps) {
+  const [values, setValues] = useState<Record<string, string>>({});
+  const [preview, setPreview] = useState('');
+  const debouncedValues = useDebounce(values, 200);
+
+  useEffect(() => {
+    setValues({});
+  }, [snippet]);
+
+  useEffect(() => {
+    if (!snippet) return;
+
+    let cancelled = false;
+    clipboardService
+      .expandSnippet(snippet.template, debouncedValues)
+      .then((text) => !cancelled && setPreview(text))
+      .catch((error) => !cancelled && setPreview(String(error)));
+    return () => {
+      cancelled = true;
+    };
+  }, [snippet, debouncedValues]);
+
+  const handleSubmit = (event: FormEvent) => {
+    event.preventDefault();
+    onSubmit(Object.fromEntries(fields.map((field) => [field, values[field] ?? ''])));
+  };
+
+  return (
+    <Dialog open={!!snippet} onOpenChange={(open) => !open && onClose()}>
+      <DialogContent>
+        <form onSubmit={handleSubmit} className='space-y-4'>
+          <DialogHeader>
+            <DialogTitle>{snippet?.name}</DialogTitle>
+            <DialogDescription>Fill in the snippet's fields; the preview shows what will be pasted.</DialogDescription>
+          </DialogHeader>
+          {fields.map((field, index) => (
+            <div key={field} className='space-y-2'>
+              <Label htmlFor={`snippet-input-${index}`}>{field}</Label>
+              <Input
+                id={`snippet-input-${index}`}
+                autoFocus={index === 0}
+                value={values[field] ?? ''}
+                onChange={(e) => setValues({ ...values, [field]: e.target.value })}
+              />
+            </div>
+          ))}
+          <pre
+            className={cn(
+              'max-h-32 whitespace-pre-wrap break-words rounded-md bg-muted p-2 text-xs',
+              scrollbarStyles
+            )}
+          >
+            {preview}
+          </pre>
+          <DialogFooter>
+            <Button type='button' variant='outline' onClick={onClose}>
+              Cancel
+            </Button>
+            <Button type='submit'>Paste</Button>
+          </DialogFooter>
+        </form>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -0,0 +1,84 @@
+import { useState } from 'react';
+import { FileText, Trash } from 'lucide-react';
+
+import {
+  ContextMenu,
+  ContextMenuContent,
+  ContextMenuItem,
+  ContextMenuTrigger,
+} from '@/components/ui/context-menu';
+import { useClipboardContext } from '@/clipboard-context';
+import { useSnippets } from '@/hooks/use-snippets';
+import { SnippetInputDialog } from '@/components/clipboard/snippet-input-dialog';
+import { Snippet } from '@/types/snippets';
+
+const MAX_VISIBLE_SNIPPETS = 5;
+
+/** Snippets matching the current search, listed above the clipboard history. */
+const SnippetList = () => {
+  const { state } = useClipboardContext();
+  const { snippets, deleteSnippet, getSnippetInputs, pasteSnippet } = useSnippets(
+    state.searchQuery,
+    MAX_VISIBLE_SNIPPETS
+  );
+  const [pendingSnippet, setPendingSnippet] = useState<Snippet | null>(null);
+  const [fields, setFields] = useState<string[]>([]);
+
+  if (!state.searchQuery.trim() || snippets.length === 0) return null;
+
+  const handlePaste = async (snippet: Snippet) => {
+    const inputs = await getSnippetInputs(snippet);
+    if (inputs.length === 0) {
+      await pasteSnippet(snippet);
+      return;
+    }
+
+    setFields(inputs);
+    setPendingSnippet(snippet);
+  };
+
+  const handleSubmit = async (inputs: Record<string, string>) => {
+    const snippet = pendingSnippet;
+    setPendingSnippet(null);
+    if (snippet) await pasteSnippet(snippet, inputs);
+  };
+
+  return (
+    <div className='border-b border-border'>
+      <div className='px-4 pt-2 pb-1 text-xs font-medium text-muted-foreground'>Snippets</div>
+      {snippets.map((snippet) => (
+        <ContextMenu key={snippet.id}>
+          <ContextMenuTrigger asChild>
+            <div
+              className='flex items-center gap-3 px-4 py-2 cursor-pointer hover:bg-accent transition-colors'
+              onClick={() => handlePaste(snippet)}
+              title='Click to paste'
+            >
+              <FileText className='h-4 w-4 flex-shrink-0 text-muted-foreground' />
+              <div className='flex-1 min-w-0'>
+                <p className='text-sm truncate'>{snippet.name}</p>
+                <p className='text-xs text-muted-foreground truncate'>{snippet.template}</p>
+              </div>
+            </div>
+          </ContextMenuTrigger>
+          <ContextMenuContent>
+            <ContextMenuItem
+              className='flex items-center text-destructive focus:bg-destructive focus:text-destructive-foreground'
+              onClick={() => deleteSnippet(snippet.id)}
+            >
+              <Trash className='h-4 w-4 mr-2' /> Delete Snippet
+            </ContextMenuItem>
+          </ContextMenuContent>
+        </ContextMenu>
+      ))}
+      <SnippetInputDialog
+        snippet={pendingSnippet}
+        fields={fields}
+        onSubmit={handleSubmit}
+        onClose={() => setPendingSnippet(null)}
+      />
+    </div>
+  );
+};
+
+export default SnippetList;
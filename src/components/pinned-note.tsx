@@ -0,0 +1,90 @@
+import { useEffect, useState } from 'react';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+import { ClipboardPaste, Copy, X } from 'lucide-react';
+
+import { Button } from '@/components/ui/button';
+import { getAppSettings } from '@/lib/app-settings';
+import { safeInvoke, scrollbarStyles } from '@/lib/utils';
+import Logger from '@/util/logger';
+import type { ClipboardEntry } from '@/types/clipboard';
+
+/** Must match `LABEL_PREFIX` in the backend's `pinned.rs`. */
+const PINNED_NOTE_LABEL_PREFIX = 'pinned-';
+
+type PinnedEntry = Pick<ClipboardEntry, 'id' | 'content' | 'contentType' | 'preview'>;
+
+export function isPinnedNoteWindow() {
+  return getCurrentWindow().label.startsWith(PINNED_NOTE_LABEL_PREFIX);
+}
+
+export function PinnedNote() {
+  const [entry, setEntry] = useState<PinnedEntry | null>(null);
+
+  useEffect(() => {
+    safeInvoke<PinnedEntry>('get_pinned_entry').then(setEntry, (error) => {
+      Logger.error('Failed to load pinned entry:', error);
+    });
+  }, []);
+
+  const copy = async () => {
+    try {
+      await safeInvoke('copy_pinned_entry');
+    } catch (error) {
+      Logger.error('Failed to copy pinned entry:', error);
+    }
+  };
+
+  const paste = async () => {
+    try {
+      const { restoreClipboard } = await getAppSettings();
+      await safeInvoke('paste_pinned_entry', { restoreClipboard });
+    } catch (error) {
+      Logger.error('Failed to paste pinned entry:', error);
+    }
+  };
+
+  const unpin = async () => {
+    try {
+      await safeInvoke('unpin_entry');
+    } catch (error) {
+      Logger.error('Failed to unpin entry:', error);
+    }
+  };
+
+  return (
+    <div className='flex flex-col h-screen bg-background border border-border rounded-md'>
+      <div
+        data-tauri-drag-region
+        className='h-8 flex items-center justify-end gap-1 px-1 border-b border-border select-none'
+      >
+        <Button variant='ghost' size='icon' className='h-6 w-6' onClick={copy} title='Copy'>
+          <Copy size={14} />
+        </Button>
+        <Button variant='ghost' size='icon' className='h-6 w-6' onClick={paste} title='Paste'>
+          <ClipboardPaste size={14} />
+        </Button>
+        <Button
+          size='icon'
+          className='h-6 w-6 bg-transparent text-foreground hover:bg-destructive hover:text-destructive-foreground'
+          onClick={unpin}
+          title='Unpin'
+        >
+          <X size={14} />
+        </Button>
+      </div>
+      <div className={`flex-1 p-2 text-sm ${scrollbarStyles}`}>
+        {entry?.contentType === 'image' ? (
+          <img
+            src={`data:image/png;base64,${entry.content}`}
+            alt='Pinned entry'
+            className='max-w-full'
+          />
+        ) : (
+          <pre className='whitespace-pre-wrap break-words font-sans select-text'>
+            {entry?.preview || entry?.content}
+          </pre>
+        )}
+      </div>
+    </div>
+  );
+}
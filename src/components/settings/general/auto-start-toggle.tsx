@@ -1,40 +1,26 @@
 import { useEffect, useState } from 'react';
-import { disable, enable, isEnabled } from '@tauri-apps/plugin-autostart';
+import { isEnabled } from '@tauri-apps/plugin-autostart';
 import { Power } from 'lucide-react';
 
 import { SettingToggle } from '@/components/settings/setting-toggle';
-import clipboardDatabase from '@/lib/db';
-import { DEFAULT_AUTO_START, SETTING_KEYS } from '@/types/settings';
+import { useAppSetting } from '@/hooks/use-app-setting';
 
 export function AutoStartToggle() {
-  const [autoStart, setAutoStart] = useState<boolean>(false);
+  // The backend registers or removes the login item when the setting changes.
+  const { value: autoStart, isLoaded, setValue: setAutoStart } = useAppSetting('autoStart');
+  const [systemEnabled, setSystemEnabled] = useState(false);
 
   useEffect(() => {
-    const loadAutoStartSetting = async () => {
-      const setting = await clipboardDatabase.getSetting(
-        SETTING_KEYS?.AUTO_START,
-        DEFAULT_AUTO_START
-      );
-      const systemEnabled = await isEnabled();
-      setAutoStart(setting && systemEnabled);
-    };
+    if (!isLoaded) return;
 
-    loadAutoStartSetting();
-  }, []);
+    isEnabled()
+      .then(setSystemEnabled)
+      .catch((error) => console.error('Failed to read autostart state:', error));
+  }, [autoStart, isLoaded]);
 
-  const handleAutoStartToggle = async (checked: boolean) => {
-    try {
-      if (checked) {
-        await enable();
-      } else {
-        await disable();
-      }
-
-      await clipboardDatabase.setSetting(SETTING_KEYS.AUTO_START, checked);
-      setAutoStart(checked);
-    } catch (error) {
-      console.error('Failed to toggle autostart:', error);
-    }
+  const handleAutoStartToggle = (checked: boolean) => {
+    setAutoStart(checked);
+    setSystemEnabled(checked);
   };
 
   return (
@@ -42,7 +28,7 @@ export function AutoStartToggle() {
       icon={Power}
       title='Auto Start'
       description='Start with system on boot'
-      checked={autoStart}
+      checked={autoStart && systemEnabled}
       onCheckedChange={handleAutoStartToggle}
     />
   );
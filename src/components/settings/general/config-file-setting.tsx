@@ -0,0 +1,39 @@
+import { FileCog } from 'lucide-react';
+
+import { Button } from '@/components/ui/button';
+import { useConfigFile } from '@/hooks/use-config-file';
+import Logger from '@/util/logger';
+
+export function ConfigFileSetting() {
+  const { status, openConfigFile } = useConfigFile();
+
+  const open = () => {
+    openConfigFile().catch((error) => Logger.error('Failed to open config file:', error));
+  };
+
+  return (
+    <div className='space-y-2 py-2'>
+      <div className='flex items-center justify-between gap-4'>
+        <div className='flex items-center gap-3 min-w-0'>
+          <div className='flex items-center justify-center w-8 h-8 rounded-lg bg-muted'>
+            <FileCog className='h-4 w-4 text-muted-foreground' />
+          </div>
+          <div className='min-w-0'>
+            <div className='font-medium text-sm'>Config File</div>
+            <div className='text-xs text-muted-foreground truncate' title={status?.path}>
+              {status?.path ?? 'Capture rules, shortcuts, retention and more'}
+            </div>
+          </div>
+        </div>
+        <Button variant='outline' size='sm' onClick={open}>
+          Open
+        </Button>
+      </div>
+      {status?.error && (
+        <div className='rounded-md bg-destructive/10 px-3 py-2 text-xs text-destructive whitespace-pre-wrap font-mono'>
+          {status.error}
+        </div>
+      )}
+    </div>
+  );
+}
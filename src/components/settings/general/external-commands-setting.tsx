@@ -0,0 +1,74 @@
+import { useState } from 'react';
+import { Plus, Terminal, Trash } from 'lucide-react';
+
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { useAppSetting } from '@/hooks/use-app-setting';
+
+export function ExternalCommandsSetting() {
+  const { value: commands, setValue: setCommands } = useAppSetting('externalCommands');
+  const [name, setName] = useState('');
+  const [commandLine, setCommandLine] = useState('');
+
+  const canAdd =
+    name.trim() !== '' &&
+    commandLine.trim() !== '' &&
+    !commands.some((command) => command.name === name.trim());
+
+  const addCommand = () => {
+    if (!canAdd) return;
+    setCommands([...commands, { name: name.trim(), commandLine: commandLine.trim() }]);
+    setName('');
+    setCommandLine('');
+  };
+
+  return (
+    <div className='space-y-3 py-2'>
+      <div className='flex items-center gap-3'>
+        <div className='flex items-center justify-center w-8 h-8 rounded-lg bg-muted'>
+          <Terminal className='h-4 w-4 text-muted-foreground' />
+        </div>
+        <div>
+          <div className='font-medium text-sm'>External Commands</div>
+          <div className='text-xs text-muted-foreground'>
+            Programs that get an entry on stdin, e.g. jq . or sort -u. No shell is involved.
+          </div>
+        </div>
+      </div>
+
+      {commands.map((command) => (
+        <div key={command.name} className='flex items-center gap-2'>
+          <div className='w-32 truncate text-sm'>{command.name}</div>
+          <code className='flex-1 truncate text-xs text-muted-foreground'>{command.commandLine}</code>
+          <Button
+            variant='ghost'
+            size='icon'
+            title='Remove'
+            onClick={() => setCommands(commands.filter((c) => c.name !== command.name))}
+          >
+            <Trash className='h-4 w-4' />
+          </Button>
+        </div>
+      ))}
+
+      <div className='flex items-center gap-2'>
+        <Input
+          className='w-32'
+          placeholder='Name'
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+        />
+        <Input
+          className='flex-1 font-mono'
+          placeholder='pandoc -f html -t markdown'
+          value={commandLine}
+          onChange={(e) => setCommandLine(e.target.value)}
+          onKeyDown={(e) => e.key === 'Enter' && addCommand()}
+        />
+        <Button variant='outline' size='icon' title='Add' disabled={!canAdd} onClick={addCommand}>
+          <Plus className='h-4 w-4' />
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,32 @@
+import { FilePen } from 'lucide-react';
+
+import { Input } from '@/components/ui/input';
+import { useAppSetting } from '@/hooks/use-app-setting';
+
+export function ExternalEditorSetting() {
+  const { value: editor, setValue: setEditor } = useAppSetting('externalEditor', 300);
+
+  return (
+    <div className='flex items-center justify-between gap-4 py-2'>
+      <div className='flex items-center gap-3'>
+        <div className='flex items-center justify-center w-8 h-8 rounded-lg bg-muted'>
+          <FilePen className='h-4 w-4 text-muted-foreground' />
+        </div>
+        <div>
+          <div className='font-medium text-sm'>External Editor</div>
+          <div className='text-xs text-muted-foreground'>
+            Command used to edit entries. Add the editor's wait flag, like code --wait, so edits
+            are picked up until you close it. Leave empty for the system default, or $VISUAL and
+            $EDITOR when Nabu was started from a terminal
+          </div>
+        </div>
+      </div>
+      <Input
+        className='w-48 font-mono'
+        placeholder='code --wait'
+        value={editor}
+        onChange={(e) => setEditor(e.target.value)}
+      />
+    </div>
+  );
+}
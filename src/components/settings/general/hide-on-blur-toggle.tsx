@@ -0,0 +1,18 @@
+import { EyeOff } from 'lucide-react';
+
+import { SettingToggle } from '@/components/settings/setting-toggle';
+import { useAppSetting } from '@/hooks/use-app-setting';
+
+export function HideOnBlurToggle() {
+  const { value: hideOnBlur, setValue: setHideOnBlur } = useAppSetting('hideOnBlur');
+
+  return (
+    <SettingToggle
+      icon={EyeOff}
+      title='Hide When Unfocused'
+      description='Hide the panel when you click outside of it'
+      checked={hideOnBlur}
+      onCheckedChange={setHideOnBlur}
+    />
+  );
+}
@@ -0,0 +1,76 @@
+import { useState } from 'react';
+import { Check, SeparatorHorizontal } from 'lucide-react';
+
+import { Button } from '@/components/ui/button';
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuSeparator,
+  DropdownMenuTrigger,
+} from '@/components/ui/dropdown-menu';
+import { Input } from '@/components/ui/input';
+import { useAppSetting } from '@/hooks/use-app-setting';
+import { JOIN_SEPARATOR_PRESETS } from '@/types/settings';
+
+export function JoinSeparatorSetting() {
+  const { value: separator, setValue: setSeparator } = useAppSetting('joinSeparator', 300);
+
+  const preset = Object.values(JOIN_SEPARATOR_PRESETS).find((p) => p.value === separator);
+  const [isCustom, setIsCustom] = useState(false);
+  const showCustomInput = isCustom || !preset;
+
+  return (
+    <div className='flex items-center justify-between py-2'>
+      <div className='flex items-center gap-3'>
+        <div className='flex items-center justify-center w-8 h-8 rounded-lg bg-muted'>
+          <SeparatorHorizontal className='h-4 w-4 text-muted-foreground' />
+        </div>
+        <div>
+          <div className='font-medium text-sm'>Join Separator</div>
+          <div className='text-xs text-muted-foreground'>
+            Placed between entries when pasting several at once
+          </div>
+        </div>
+      </div>
+      <div className='flex items-center gap-2'>
+        {showCustomInput && (
+          <Input
+            className='h-9 w-24'
+            value={separator}
+            onChange={(e) => setSeparator(e.target.value)}
+            placeholder=' | '
+          />
+        )}
+        <DropdownMenu>
+          <DropdownMenuTrigger asChild>
+            <Button variant='outline'>{showCustomInput ? 'Custom' : preset?.label}</Button>
+          </DropdownMenuTrigger>
+          <DropdownMenuContent align='end' className='w-40'>
+            {Object.values(JOIN_SEPARATOR_PRESETS).map((option) => (
+              <DropdownMenuItem
+                key={option.label}
+                onClick={() => {
+                  setIsCustom(false);
+                  setSeparator(option.value);
+                }}
+                className='flex items-center justify-between cursor-pointer'
+              >
+                <span>{option.label}</span>
+                {!showCustomInput && preset === option && <Check className='h-4 w-4' />}
+              </DropdownMenuItem>
+            ))}
+            <DropdownMenuSeparator />
+            <DropdownMenuItem
+              onClick={() => setIsCustom(true)}
+              className='flex items-center justify-between cursor-pointer'
+            >
+              <span>Custom</span>
+              {showCustomInput && <Check className='h-4 w-4' />}
+            </DropdownMenuItem>
+          </DropdownMenuContent>
+        </DropdownMenu>
+      </div>
+    </div>
+  );
+}
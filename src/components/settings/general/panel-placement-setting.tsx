@@ -0,0 +1,46 @@
+import { Check, Move } from 'lucide-react';
+
+import { Button } from '@/components/ui/button';
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuTrigger,
+} from '@/components/ui/dropdown-menu';
+import { useAppSetting } from '@/hooks/use-app-setting';
+import { PANEL_PLACEMENTS, type PanelPlacement } from '@/types/settings';
+
+export function PanelPlacementSetting() {
+  const { value: placement, setValue: setPlacement } = useAppSetting('panelPlacement');
+
+  return (
+    <div className='flex items-center justify-between py-2'>
+      <div className='flex items-center gap-3'>
+        <div className='flex items-center justify-center w-8 h-8 rounded-lg bg-muted'>
+          <Move className='h-4 w-4 text-muted-foreground' />
+        </div>
+        <div>
+          <div className='font-medium text-sm'>Panel Placement</div>
+          <div className='text-xs text-muted-foreground'>Where the panel appears when opened</div>
+        </div>
+      </div>
+      <DropdownMenu>
+        <DropdownMenuTrigger asChild>
+          <Button variant='outline'>{PANEL_PLACEMENTS[placement]}</Button>
+        </DropdownMenuTrigger>
+        <DropdownMenuContent align='end' className='w-56'>
+          {(Object.keys(PANEL_PLACEMENTS) as PanelPlacement[]).map((option) => (
+            <DropdownMenuItem
+              key={option}
+              onClick={() => setPlacement(option)}
+              className='flex items-center justify-between cursor-pointer'
+            >
+              <span>{PANEL_PLACEMENTS[option]}</span>
+              {placement === option && <Check className='h-4 w-4' />}
+            </DropdownMenuItem>
+          ))}
+        </DropdownMenuContent>
+      </DropdownMenu>
+    </div>
+  );
+}
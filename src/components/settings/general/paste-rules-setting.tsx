@@ -0,0 +1,103 @@
+import { useState } from 'react';
+import { ClipboardPaste, Plus, Trash } from 'lucide-react';
+
+import { Button } from '@/components/ui/button';
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuTrigger,
+} from '@/components/ui/dropdown-menu';
+import { Input } from '@/components/ui/input';
+import { useAppSetting } from '@/hooks/use-app-setting';
+import { PASTE_STRATEGIES, type PasteStrategy } from '@/types/settings';
+
+function StrategyDropdown({
+  strategy,
+  onChange,
+}: {
+  strategy: PasteStrategy;
+  onChange: (strategy: PasteStrategy) => void;
+}) {
+  return (
+    <DropdownMenu>
+      <DropdownMenuTrigger asChild>
+        <Button variant='outline' className='w-36'>
+          {PASTE_STRATEGIES[strategy]}
+        </Button>
+      </DropdownMenuTrigger>
+      <DropdownMenuContent align='end' className='w-40'>
+        {(Object.keys(PASTE_STRATEGIES) as PasteStrategy[]).map((option) => (
+          <DropdownMenuItem key={option} onClick={() => onChange(option)}>
+            {PASTE_STRATEGIES[option]}
+          </DropdownMenuItem>
+        ))}
+      </DropdownMenuContent>
+    </DropdownMenu>
+  );
+}
+
+export function PasteRulesSetting() {
+  const { value: rules, setValue: setRules } = useAppSetting('pasteRules');
+  const [app, setApp] = useState('');
+  const [strategy, setStrategy] = useState<PasteStrategy>('ctrl_shift_v');
+
+  const canAdd = app.trim() !== '';
+
+  const addRule = () => {
+    if (!canAdd) return;
+    setRules([...rules, { app: app.trim(), strategy }]);
+    setApp('');
+  };
+
+  return (
+    <div className='space-y-3 py-2'>
+      <div className='flex items-center gap-3'>
+        <div className='flex items-center justify-center w-8 h-8 rounded-lg bg-muted'>
+          <ClipboardPaste className='h-4 w-4 text-muted-foreground' />
+        </div>
+        <div>
+          <div className='font-medium text-sm'>Paste Rules</div>
+          <div className='text-xs text-muted-foreground'>
+            How to paste into an app, by process name or window class. Checked after nabu.toml and
+            before the built-in rules for terminals.
+          </div>
+        </div>
+      </div>
+
+      {rules.map((rule, index) => (
+        <div key={index} className='flex items-center gap-2'>
+          <code className='flex-1 truncate text-sm'>{rule.app}</code>
+          <StrategyDropdown
+            strategy={rule.strategy}
+            onChange={(changed) =>
+              setRules(rules.map((other, i) => (i === index ? { ...rule, strategy: changed } : other)))
+            }
+          />
+          <Button
+            variant='ghost'
+            size='icon'
+            title='Remove'
+            onClick={() => setRules(rules.filter((_, i) => i !== index))}
+          >
+            <Trash className='h-4 w-4' />
+          </Button>
+        </div>
+      ))}
+
+      <div className='flex items-center gap-2'>
+        <Input
+          className='flex-1 font-mono'
+          placeholder='gnome-terminal-server'
+          value={app}
+          onChange={(e) => setApp(e.target.value)}
+          onKeyDown={(e) => e.key === 'Enter' && addRule()}
+        />
+        <StrategyDropdown strategy={strategy} onChange={setStrategy} />
+        <Button variant='outline' size='icon' title='Add' disabled={!canAdd} onClick={addRule}>
+          <Plus className='h-4 w-4' />
+        </Button>
+      </div>
+    </div>
+  );
+}
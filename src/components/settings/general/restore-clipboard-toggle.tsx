@@ -0,0 +1,19 @@
+import { ClipboardCheck } from 'lucide-react';
+
+import { SettingToggle } from '@/components/settings/setting-toggle';
+import { useAppSetting } from '@/hooks/use-app-setting';
+
+export function RestoreClipboardToggle() {
+  const { value: restoreClipboard, setValue: setRestoreClipboard } =
+    useAppSetting('restoreClipboard');
+
+  return (
+    <SettingToggle
+      icon={ClipboardCheck}
+      title='Restore Clipboard'
+      description='Put back your previous clipboard after pasting from history'
+      checked={restoreClipboard}
+      onCheckedChange={setRestoreClipboard}
+    />
+  );
+}
@@ -0,0 +1,40 @@
+import { Keyboard } from 'lucide-react';
+
+import { Input } from '@/components/ui/input';
+import { useAppSetting } from '@/hooks/use-app-setting';
+
+/** Must match `MAX_TYPING_DELAY_MS` in the backend's `settings.rs`. */
+const MAX_TYPING_DELAY_MS = 1000;
+
+export function TypingDelaySetting() {
+  const { value: delayMs, setValue: setDelayMs } = useAppSetting('typingDelayMs', 300);
+
+  return (
+    <div className='flex items-center justify-between py-2'>
+      <div className='flex items-center gap-3'>
+        <div className='flex items-center justify-center w-8 h-8 rounded-lg bg-muted'>
+          <Keyboard className='h-4 w-4 text-muted-foreground' />
+        </div>
+        <div>
+          <div className='font-medium text-sm'>Typing Delay</div>
+          <div className='text-xs text-muted-foreground'>
+            Pause between characters when typing an entry out. Press Esc to stop typing.
+          </div>
+        </div>
+      </div>
+      <div className='flex items-center gap-2'>
+        <Input
+          type='number'
+          min={0}
+          max={MAX_TYPING_DELAY_MS}
+          className='h-9 w-24'
+          value={delayMs}
+          onChange={(e) =>
+            setDelayMs(Math.min(MAX_TYPING_DELAY_MS, Math.max(0, Number(e.target.value))))
+          }
+        />
+        <span className='text-sm text-muted-foreground'>ms</span>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,177 @@
+import { useState } from 'react';
+import { ArrowDown, ArrowUp, Clipboard, Clock, Keyboard, Plus, Trash } from 'lucide-react';
+
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { ShortcutRecorder } from '@/components/settings/shortcuts/shortcut-recorder';
+import { type MacroStep, type PasteMacro } from '@/types/macros';
+
+interface MacroEditorProps {
+  initial: PasteMacro;
+  onSave: (pasteMacro: PasteMacro) => Promise<void>;
+  onCancel: () => void;
+}
+
+const NEW_STEPS: Record<MacroStep['type'], MacroStep> = {
+  paste: { type: 'paste', entry: { id: 0, content: '', contentType: 'text' } },
+  key: { type: 'key', key: 'Tab', modifiers: [] },
+  wait: { type: 'wait', ms: 500 },
+};
+
+const STEP_ICONS = { paste: Clipboard, key: Keyboard, wait: Clock };
+
+function StepInput({ step, onChange }: { step: MacroStep; onChange: (step: MacroStep) => void }) {
+  switch (step.type) {
+    case 'paste':
+      if (step.entry.contentType !== 'text') {
+        return (
+          <div className='flex-1 truncate text-sm text-muted-foreground'>
+            {step.entry.preview || `${step.entry.contentType} entry`}
+          </div>
+        );
+      }
+      return (
+        <textarea
+          className='flex-1 min-h-9 rounded-md border border-input bg-transparent dark:bg-input/30 px-3 py-1.5 text-sm outline-none focus-visible:border-ring focus-visible:ring-ring/50 focus-visible:ring-[3px]'
+          rows={1}
+          placeholder='Text to paste'
+          value={step.entry.content}
+          onChange={(e) =>
+            onChange({ ...step, entry: { ...step.entry, content: e.target.value, preview: undefined } })
+          }
+        />
+      );
+    case 'key':
+      return (
+        <ShortcutRecorder
+          className='flex-1'
+          modifiers={step.modifiers ?? []}
+          keyCode={step.key}
+          onShortcutChange={(modifiers, key) => onChange({ ...step, modifiers, key })}
+        />
+      );
+    case 'wait':
+      return (
+        <div className='flex flex-1 items-center gap-2'>
+          <Input
+            type='number'
+            min={0}
+            className='w-28'
+            value={step.ms}
+            onChange={(e) => onChange({ ...step, ms: Math.max(0, Number(e.target.value)) })}
+          />
+          <span className='text-sm text-muted-foreground'>ms</span>
+        </div>
+      );
+  }
+}
+
+/** Edits a copy of a macro; nothing is saved until Save is pressed. */
+export function MacroEditor({ initial, onSave, onCancel }: MacroEditorProps) {
+  const [draft, setDraft] = useState<PasteMacro>(initial);
+  const [error, setError] = useState('');
+
+  const setSteps = (steps: MacroStep[]) => setDraft({ ...draft, steps });
+
+  const moveStep = (index: number, offset: number) => {
+    const steps = [...draft.steps];
+    [steps[index], steps[index + offset]] = [steps[index + offset], steps[index]];
+    setSteps(steps);
+  };
+
+  const handleSave = async () => {
+    try {
+      await onSave({ ...draft, name: draft.name.trim() });
+      setError('');
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className='space-y-3 rounded-lg border p-3'>
+      <div className='flex items-center gap-2'>
+        <Input
+          className='flex-1'
+          placeholder='Macro name'
+          value={draft.name}
+          onChange={(e) => setDraft({ ...draft, name: e.target.value })}
+        />
+        <Input
+          type='number'
+          min={0}
+          className='w-24'
+          title='Delay between steps'
+          value={draft.stepDelayMs}
+          onChange={(e) => setDraft({ ...draft, stepDelayMs: Math.max(0, Number(e.target.value)) })}
+        />
+        <span className='text-sm text-muted-foreground'>ms between steps</span>
+      </div>
+
+      {draft.steps.map((step, index) => {
+        const Icon = STEP_ICONS[step.type];
+        return (
+          <div key={index} className='flex items-center gap-2'>
+            <Icon className='h-4 w-4 flex-shrink-0 text-muted-foreground' />
+            <StepInput
+              step={step}
+              onChange={(changed) =>
+                setSteps(draft.steps.map((other, i) => (i === index ? changed : other)))
+              }
+            />
+            <Button
+              variant='ghost'
+              size='icon'
+              title='Move up'
+              disabled={index === 0}
+              onClick={() => moveStep(index, -1)}
+            >
+              <ArrowUp className='h-4 w-4' />
+            </Button>
+            <Button
+              variant='ghost'
+              size='icon'
+              title='Move down'
+              disabled={index === draft.steps.length - 1}
+              onClick={() => moveStep(index, 1)}
+            >
+              <ArrowDown className='h-4 w-4' />
+            </Button>
+            <Button
+              variant='ghost'
+              size='icon'
+              title='Remove step'
+              onClick={() => setSteps(draft.steps.filter((_, i) => i !== index))}
+            >
+              <Trash className='h-4 w-4' />
+            </Button>
+          </div>
+        );
+      })}
+
+      <div className='flex items-center gap-2'>
+        {(Object.keys(NEW_STEPS) as MacroStep['type'][]).map((type) => (
+          <Button
+            key={type}
+            variant='outline'
+            size='sm'
+            onClick={() => setSteps([...draft.steps, NEW_STEPS[type]])}
+          >
+            <Plus className='h-4 w-4' />
+            {type === 'paste' ? 'Paste' : type === 'key' ? 'Key' : 'Wait'}
+          </Button>
+        ))}
+        <div className='ml-auto flex gap-2'>
+          <Button variant='ghost' size='sm' onClick={onCancel}>
+            Cancel
+          </Button>
+          <Button size='sm' disabled={draft.name.trim() === ''} onClick={handleSave}>
+            Save
+          </Button>
+        </div>
+      </div>
+
+      {error && <p className='text-sm text-destructive'>{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,105 @@
+import { useState } from 'react';
+import { ListVideo, Pencil, Plus, Trash } from 'lucide-react';
+
+import { Button } from '@/components/ui/button';
+import { MacroEditor } from '@/components/settings/macros/macro-editor';
+import { GlobalShortcutSetting } from '@/components/settings/shortcuts/global-shortcut-setting';
+import { usePasteMacros } from '@/hooks/use-paste-macros';
+import { DEFAULT_MACRO_STEP_DELAY_MS, type PasteMacro } from '@/types/macros';
+import Logger from '@/util/logger';
+
+export function PasteMacrosSetting() {
+  const { macros, saveMacro, deleteMacro, nextMacroId } = usePasteMacros();
+  const [editing, setEditing] = useState<PasteMacro | null>(null);
+
+  const isNew = editing !== null && !macros.some((pasteMacro) => pasteMacro.id === editing.id);
+
+  const handleSave = async (pasteMacro: PasteMacro) => {
+    await saveMacro(pasteMacro);
+    setEditing(null);
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await deleteMacro(id);
+    } catch (error) {
+      Logger.error('Failed to delete paste macro:', error);
+    }
+  };
+
+  return (
+    <div className='space-y-3 py-2'>
+      <div className='flex items-center justify-between'>
+        <div className='flex items-center gap-3'>
+          <div className='flex items-center justify-center w-8 h-8 rounded-lg bg-muted'>
+            <ListVideo className='h-4 w-4 text-muted-foreground' />
+          </div>
+          <div>
+            <div className='font-medium text-sm'>Paste Macros</div>
+            <div className='text-xs text-muted-foreground'>
+              Pastes, key presses and pauses run in order in the window the panel was opened from
+            </div>
+          </div>
+        </div>
+        <Button
+          variant='outline'
+          size='icon'
+          title='New macro'
+          disabled={editing !== null}
+          onClick={() =>
+            setEditing({
+              id: nextMacroId(),
+              name: '',
+              steps: [],
+              stepDelayMs: DEFAULT_MACRO_STEP_DELAY_MS,
+            })
+          }
+        >
+          <Plus className='h-4 w-4' />
+        </Button>
+      </div>
+
+      {macros.map((pasteMacro) =>
+        editing?.id === pasteMacro.id ? (
+          <MacroEditor
+            key={pasteMacro.id}
+            initial={editing}
+            onSave={handleSave}
+            onCancel={() => setEditing(null)}
+          />
+        ) : (
+          <div key={pasteMacro.id} className='space-y-2 rounded-lg border p-3'>
+            <div className='flex items-center gap-2'>
+              <div className='flex-1 truncate text-sm font-medium'>{pasteMacro.name}</div>
+              <div className='text-xs text-muted-foreground'>
+                {pasteMacro.steps.length} {pasteMacro.steps.length === 1 ? 'step' : 'steps'}
+              </div>
+              <Button
+                variant='ghost'
+                size='icon'
+                title='Edit'
+                disabled={editing !== null}
+                onClick={() => setEditing(pasteMacro)}
+              >
+                <Pencil className='h-4 w-4' />
+              </Button>
+              <Button
+                variant='ghost'
+                size='icon'
+                title='Delete'
+                onClick={() => handleDelete(pasteMacro.id)}
+              >
+                <Trash className='h-4 w-4' />
+              </Button>
+            </div>
+            <GlobalShortcutSetting action={{ run_macro: pasteMacro.id }} label='Shortcut' />
+          </div>
+        )
+      )}
+
+      {isNew && editing && (
+        <MacroEditor initial={editing} onSave={handleSave} onCancel={() => setEditing(null)} />
+      )}
+    </div>
+  );
+}
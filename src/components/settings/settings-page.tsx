@@ -1,5 +1,6 @@
-import { ArrowLeft, Keyboard, Settings, Trash2 } from 'lucide-react';
-import { useNavigate } from 'react-router';
+import { emit } from '@tauri-apps/api/event';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+import { Keyboard, ListVideo, Settings, Trash2, WandSparkles } from 'lucide-react';
 import { useState } from 'react';
 
 import { Button } from '@/components/ui/button';
@@ -19,14 +20,31 @@ import { ThemeModeToggle } from '@/components/settings/general/theme-mode-toggle
 import { SettingSection } from '@/components/settings/setting-section';
 import { ThemeColorSetting } from '@/components/settings/general/theme-color-setting';
 import { AutoStartToggle } from '@/components/settings/general/auto-start-toggle';
+import { RestoreClipboardToggle } from '@/components/settings/general/restore-clipboard-toggle';
+import { HideOnBlurToggle } from '@/components/settings/general/hide-on-blur-toggle';
+import { PanelPlacementSetting } from '@/components/settings/general/panel-placement-setting';
+import { JoinSeparatorSetting } from '@/components/settings/general/join-separator-setting';
+import { PasteRulesSetting } from '@/components/settings/general/paste-rules-setting';
+import { TypingDelaySetting } from '@/components/settings/general/typing-delay-setting';
+import { ExternalCommandsSetting } from '@/components/settings/general/external-commands-setting';
+import { ExternalEditorSetting } from '@/components/settings/general/external-editor-setting';
+import { ConfigFileSetting } from '@/components/settings/general/config-file-setting';
+import { PasteMacrosSetting } from '@/components/settings/macros/paste-macros-setting';
 import { KeyboardNavigationShortcuts } from '@/components/settings/shortcuts/keyboard-navigation-shortcuts';
 
+/** Must match `SETTINGS_LABEL` in the backend's `settings_window.rs`. */
+const SETTINGS_WINDOW_LABEL = 'settings';
+
+export function isSettingsWindow() {
+  return getCurrentWindow().label === SETTINGS_WINDOW_LABEL;
+}
+
 export default function SettingsPage() {
-  const navigate = useNavigate();
   const [isConfirmOpen, setIsConfirmOpen] = useState<boolean>(false);
 
   const handleClearHistory = async () => {
     await clipboardDatabase.clearAllEntries(true);
+    await emit('history-cleared');
     setIsConfirmOpen(false);
   };
 
@@ -34,9 +52,6 @@ export default function SettingsPage() {
     <div className={`h-screen bg-background overflow-y-auto ${scrollbarStyles}`}>
       <div className='container max-w-2xl mx-auto py-6 px-4'>
         <div className='flex items-center gap-4 mb-8'>
-          <Button variant='ghost' size='icon' onClick={() => navigate('/')} title='Back'>
-            <ArrowLeft className='h-4 w-4' />
-          </Button>
           <div>
             <h1 className='text-2xl font-bold'>Settings</h1>
             <p className='text-sm text-muted-foreground'>Manage your app preferences</p>
@@ -52,6 +67,30 @@ export default function SettingsPage() {
             <ThemeColorSetting />
             <ThemeModeToggle />
             <AutoStartToggle />
+            <HideOnBlurToggle />
+            <PanelPlacementSetting />
+            <RestoreClipboardToggle />
+            <JoinSeparatorSetting />
+            <TypingDelaySetting />
+            <PasteRulesSetting />
+            <ConfigFileSetting />
+          </SettingSection>
+
+          <SettingSection
+            icon={WandSparkles}
+            title='Transforms'
+            description='External programs for transforming and editing entries'
+          >
+            <ExternalCommandsSetting />
+            <ExternalEditorSetting />
+          </SettingSection>
+
+          <SettingSection
+            icon={ListVideo}
+            title='Macros'
+            description='Sequences of pastes and key presses, run from the panel or a shortcut'
+          >
+            <PasteMacrosSetting />
           </SettingSection>
 
           <SettingSection
@@ -0,0 +1,100 @@
+import { X } from 'lucide-react';
+
+import { Button } from '@/components/ui/button';
+import { ShortcutRecorder } from '@/components/settings/shortcuts/shortcut-recorder';
+import { useAppSetting } from '@/hooks/use-app-setting';
+import { safeInvoke } from '@/lib/utils';
+import { type KeyBinding } from '@/types/settings';
+import { type ShortcutAction } from '@/types/shortcuts';
+
+interface GlobalShortcutSettingProps {
+  action: ShortcutAction;
+  label: string;
+}
+
+function isSameAction(a: ShortcutAction, b: ShortcutAction) {
+  return JSON.stringify(a) === JSON.stringify(b);
+}
+
+/** Splits an accelerator like `ctrl+KeyK KeyV` into one binding per chord stage. */
+function parseAccelerator(accelerator: string): KeyBinding[] {
+  return accelerator
+    .split(' ')
+    .filter(Boolean)
+    .map((stage) => {
+      const parts = stage.split('+');
+      const key = parts.pop() ?? '';
+      return { modifiers: parts, key };
+    });
+}
+
+function formatAccelerator(stages: KeyBinding[]) {
+  return stages.map(({ modifiers, key }) => [...modifiers, key].join('+')).join(' ');
+}
+
+/**
+ * A global shortcut for one action, with an optional second key that makes it
+ * a chord. Saved in the settings store; `nabu.toml` takes precedence.
+ */
+export function GlobalShortcutSetting({ action, label }: GlobalShortcutSettingProps) {
+  const { value: globalShortcuts } = useAppSetting('globalShortcuts');
+
+  const saved = globalShortcuts.find((shortcut) => isSameAction(shortcut.action, action));
+  const [first, second] = saved ? parseAccelerator(saved.accelerator) : [];
+
+  const save = async (stages: KeyBinding[]) => {
+    await safeInvoke('set_global_shortcut', {
+      action,
+      accelerator: stages.length > 0 ? formatAccelerator(stages) : null,
+    });
+  };
+
+  return (
+    <div className='space-y-2'>
+      <div className='flex items-center gap-2'>
+        <ShortcutRecorder
+          className='flex-1'
+          modifiers={first?.modifiers ?? []}
+          keyCode={first?.key ?? ''}
+          onShortcutChange={(modifiers, key) =>
+            save(second ? [{ modifiers, key }, second] : [{ modifiers, key }])
+          }
+          label={label}
+        />
+        <Button
+          variant='ghost'
+          size='icon'
+          onClick={() => save([])}
+          disabled={!first}
+          title='Remove shortcut'
+          type='button'
+        >
+          <X className='h-4 w-4' />
+        </Button>
+      </div>
+
+      {first && (
+        <div className='flex items-center gap-2 pl-4'>
+          <ShortcutRecorder
+            className='flex-1'
+            modifiers={second?.modifiers ?? []}
+            keyCode={second?.key ?? ''}
+            onShortcutChange={(modifiers, key) => save([first, { modifiers, key }])}
+            label='Then (optional)'
+            placeholder='No second key'
+          />
+          <Button
+            variant='ghost'
+            size='icon'
+            onClick={() => save([first])}
+            disabled={!second}
+            title='Remove second key'
+            type='button'
+          >
+            <X className='h-4 w-4' />
+          </Button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,34 +1,19 @@
-import { useClipboardContext } from '@/clipboard-context';
+import { useAppSetting } from '@/hooks/use-app-setting';
 import { useClipboardActions } from '@/hooks/use-clipboard-actions';
-import { useSetting } from '@/hooks/use-setting';
-import { DEFAULT_KEYBOARD_NAVIGATION, SETTING_KEYS } from '@/types/settings';
+import { useKeyboardNavigation } from '@/hooks/use-keyboard-navigation';
+import { GlobalShortcutSetting } from '@/components/settings/shortcuts/global-shortcut-setting';
 import { ShortcutRecorder } from '@/components/settings/shortcuts/shortcut-recorder';
 import { WindowsClipboardShortcutToggle } from '@/components/settings/shortcuts/windows-clipboard-shortcut-toggle';
 import { DEFAULT_SHORTCUTS } from '@/types/shortcuts';
 
 export function KeyboardNavigationShortcuts() {
-  const { state } = useClipboardContext();
-  const { currentShortcut } = state;
+  const { value: currentShortcut } = useAppSetting('toggleShortcut');
   const { updateShortcut } = useClipboardActions();
 
-  const {
-    value: navSettings,
-    setValue: setNavSettings,
-    isLoaded,
-  } = useSetting(SETTING_KEYS.KEYBOARD_NAVIGATION, DEFAULT_KEYBOARD_NAVIGATION, 300);
+  const { value: navSettings, setShortcut, isLoaded } = useKeyboardNavigation(300);
 
   const handleLaunchShortcutChange = async (modifiers: string[], key: string) => {
-    await updateShortcut({ modifiers, key, label: DEFAULT_SHORTCUTS.launch.label });
-  };
-
-  const handleNavShortcutChange = async (shortcutKey: string, modifiers: string[], key: string) => {
-    setNavSettings({
-      ...navSettings,
-      shortcuts: {
-        ...navSettings.shortcuts,
-        [shortcutKey]: { ...navSettings.shortcuts[shortcutKey], modifiers, key },
-      },
-    });
+    await updateShortcut({ modifiers, key });
   };
 
   if (!isLoaded) {
@@ -43,6 +28,8 @@ export function KeyboardNavigationShortcuts() {
         onShortcutChange={handleLaunchShortcutChange}
         label={DEFAULT_SHORTCUTS.launch.label}
       />
+      <GlobalShortcutSetting action='paste_plain' label='Paste Clipboard as Plain Text' />
+      <GlobalShortcutSetting action='paste_next' label='Paste Next in Queue' />
 
       {Object.entries(DEFAULT_SHORTCUTS)
         .filter(([key]) => key !== 'launch')
@@ -54,9 +41,7 @@ export function KeyboardNavigationShortcuts() {
               key={key}
               modifiers={shortcut?.modifiers || config.modifiers}
               keyCode={shortcut?.key || config.key}
-              onShortcutChange={async (modifiers, keyCode) => {
-                await handleNavShortcutChange(key, modifiers, keyCode);
-              }}
+              onShortcutChange={(modifiers, keyCode) => setShortcut(key, modifiers, keyCode)}
               label={config.label}
             />
           );
@@ -9,6 +9,8 @@ interface ShortcutRecorderProps {
   keyCode: string;
   onShortcutChange: (modifiers: string[], key: string) => Promise<void> | void;
   label?: string;
+  /** Shown instead of the keys when no shortcut is set. */
+  placeholder?: string;
   className?: string;
 }
 
@@ -17,6 +19,7 @@ export function ShortcutRecorder({
   keyCode,
   onShortcutChange,
   label,
+  placeholder = 'Not set',
   className = '',
 }: ShortcutRecorderProps) {
   const { isCapturing, error, startCapture } = useShortcutRecorder(onShortcutChange);
@@ -43,6 +46,8 @@ export function ShortcutRecorder({
             <span className='text-sm text-muted-foreground whitespace-nowrap'>
               Press keys now... (ESC to cancel)
             </span>
+          ) : !keyCode ? (
+            <span className='text-sm text-muted-foreground whitespace-nowrap'>{placeholder}</span>
           ) : (
             <KbdGroup>
               {shortcutParts.map((part, index) => (
@@ -1,21 +1,21 @@
 import { createContext, useContext, useEffect, useState } from 'react';
 
+import { useAppSetting } from '@/hooks/use-app-setting';
+import { updateAppSettings } from '@/lib/app-settings';
+import Logger from '@/util/logger';
+import { type ThemeMode } from '@/types/settings';
 import { type ColorTheme, DEFAULT_THEME, THEMES } from '@/types/theme';
 
-type Mode = 'dark' | 'light' | 'system';
-
 type ThemeProviderProps = {
   children: React.ReactNode;
-  defaultMode?: Mode;
+  defaultMode?: ThemeMode;
   defaultColorTheme?: ColorTheme;
-  modeStorageKey?: string;
-  themeStorageKey?: string;
 };
 
 type ThemeProviderState = {
-  mode: Mode;
+  mode: ThemeMode;
   colorTheme: ColorTheme;
-  setMode: (mode: Mode) => void;
+  setMode: (mode: ThemeMode) => void;
   setColorTheme: (theme: ColorTheme) => void;
 };
 
@@ -26,25 +26,64 @@ const initialState: ThemeProviderState = {
   setColorTheme: () => null,
 };
 
+/** Where the theme was kept before it moved to the settings store. */
+const LEGACY_MODE_KEY = 'vite-ui-mode';
+const LEGACY_COLOR_THEME_KEY = 'vite-ui-color-theme';
+
+/** The last theme seen, so windows open in it before the backend has answered. */
+const CACHED_MODE_KEY = 'nabu-theme-mode';
+const CACHED_COLOR_THEME_KEY = 'nabu-color-theme';
+
 const ThemeProviderContext = createContext<ThemeProviderState>(initialState);
 
+function isColorTheme(name: string | null): name is ColorTheme {
+  return THEMES.some((t) => t.name === name);
+}
+
 export function ThemeProvider({
   children,
   defaultMode = 'system',
   defaultColorTheme = DEFAULT_THEME,
-  modeStorageKey = 'vite-ui-mode',
-  themeStorageKey = 'vite-ui-color-theme',
   ...props
 }: ThemeProviderProps) {
-  const [mode, setMode] = useState<Mode>(
-    () => (localStorage.getItem(modeStorageKey) as Mode) || defaultMode
-  );
+  const modeSetting = useAppSetting('themeMode');
+  const colorThemeSetting = useAppSetting('colorTheme');
 
-  const [colorTheme, setColorTheme] = useState<ColorTheme>(() => {
-    const stored = localStorage.getItem(themeStorageKey) as ColorTheme;
-    return THEMES.find((t) => t.name === stored) ? stored : defaultColorTheme;
+  const [cachedMode] = useState<ThemeMode>(
+    () => (localStorage.getItem(CACHED_MODE_KEY) as ThemeMode) || defaultMode
+  );
+  const [cachedColorTheme] = useState<ColorTheme>(() => {
+    const cached = localStorage.getItem(CACHED_COLOR_THEME_KEY);
+    return isColorTheme(cached) ? cached : defaultColorTheme;
   });
 
+  const mode = modeSetting.isLoaded ? modeSetting.value : cachedMode;
+  const colorTheme = colorThemeSetting.isLoaded ? colorThemeSetting.value : cachedColorTheme;
+
+  useEffect(() => {
+    const legacyMode = localStorage.getItem(LEGACY_MODE_KEY) as ThemeMode | null;
+    const legacyColorTheme = localStorage.getItem(LEGACY_COLOR_THEME_KEY);
+    if (!legacyMode && !legacyColorTheme) return;
+
+    updateAppSettings({
+      ...(legacyMode && { themeMode: legacyMode }),
+      ...(isColorTheme(legacyColorTheme) && { colorTheme: legacyColorTheme }),
+    })
+      .then(() => {
+        localStorage.removeItem(LEGACY_MODE_KEY);
+        localStorage.removeItem(LEGACY_COLOR_THEME_KEY);
+      })
+      .catch((error) => Logger.error('Failed to move theme to settings:', error));
+  }, []);
+
+  useEffect(() => {
+    if (modeSetting.isLoaded) localStorage.setItem(CACHED_MODE_KEY, mode);
+  }, [mode, modeSetting.isLoaded]);
+
+  useEffect(() => {
+    if (colorThemeSetting.isLoaded) localStorage.setItem(CACHED_COLOR_THEME_KEY, colorTheme);
+  }, [colorTheme, colorThemeSetting.isLoaded]);
+
   useEffect(() => {
     const root = window.document.documentElement;
 
@@ -75,14 +114,8 @@ export function ThemeProvider({
   const value = {
     mode,
     colorTheme,
-    setMode: (mode: Mode) => {
-      localStorage.setItem(modeStorageKey, mode);
-      setMode(mode);
-    },
-    setColorTheme: (theme: ColorTheme) => {
-      localStorage.setItem(themeStorageKey, theme);
-      setColorTheme(theme);
-    },
+    setMode: modeSetting.setValue,
+    setColorTheme: colorThemeSetting.setValue,
   };
 
   return (
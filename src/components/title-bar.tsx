@@ -1,12 +1,18 @@
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { Cog, Minus, X } from 'lucide-react';
-import { useNavigate } from 'react-router';
 
 import { Button } from '@/components/ui/button';
+import { safeInvoke } from '@/lib/utils';
 import Logger from '@/util/logger';
 
 export function TitleBar() {
-  const navigate = useNavigate();
+  const openSettings = async () => {
+    try {
+      await safeInvoke('open_settings');
+    } catch (error) {
+      Logger.error('Failed to open settings:', error);
+    }
+  };
 
   const minimizeWindow = async () => {
     try {
@@ -37,7 +43,7 @@ export function TitleBar() {
         <Button
           variant='ghost'
           size='icon'
-          onClick={() => navigate('/settings')}
+          onClick={openSettings}
           title='Settings'
           className='h-8 w-8'
         >
@@ -0,0 +1,53 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+
+import useDebounce from '@/hooks/use-debounce';
+import { getAppSettings, updateAppSettings } from '@/lib/app-settings';
+import Logger from '@/util/logger';
+import { type AppSettings, DEFAULT_APP_SETTINGS } from '@/types/settings';
+
+/**
+ * One setting from the backend's settings store, kept in sync with changes made
+ * in other windows or by the backend.
+ */
+export function useAppSetting<K extends keyof AppSettings>(key: K, delay: number = 0) {
+  const [localValue, setLocalValue] = useState<AppSettings[K]>(DEFAULT_APP_SETTINGS[key]);
+  const [savedValue, setSavedValue] = useState<AppSettings[K]>(DEFAULT_APP_SETTINGS[key]);
+  const [isLoaded, setIsLoaded] = useState(false);
+  const debouncedValue = useDebounce(localValue, delay);
+
+  useEffect(() => {
+    getAppSettings()
+      .then((settings) => {
+        setLocalValue(settings[key]);
+        setSavedValue(settings[key]);
+        setIsLoaded(true);
+      })
+      .catch((error) => Logger.error(`Failed to load setting ${key}:`, error));
+
+    const unlisten = listen<AppSettings>('settings-changed', (event) => {
+      setLocalValue(event.payload[key]);
+      setSavedValue(event.payload[key]);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [key]);
+
+  useEffect(() => {
+    if (!isLoaded || JSON.stringify(debouncedValue) === JSON.stringify(savedValue)) return;
+
+    updateAppSettings({ [key]: debouncedValue }).catch((error) => {
+      Logger.error(`Failed to save setting ${key}:`, error);
+      // Show what is actually in effect.
+      setLocalValue(savedValue);
+    });
+  }, [debouncedValue, isLoaded, key]);
+
+  return {
+    value: localValue,
+    isLoaded,
+    setValue: setLocalValue,
+  };
+}
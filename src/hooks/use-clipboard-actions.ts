@@ -1,33 +1,31 @@
 import { MouseEvent } from 'react';
 import { useQueryClient } from '@tanstack/react-query';
+import { emit } from '@tauri-apps/api/event';
 
 import { useClipboardContext } from '@/clipboard-context';
 import { useKeyboardShortcut } from '@/context/keyboard-context';
-import { useSetting } from '@/hooks/use-setting';
+import { useKeyboardNavigation } from '@/hooks/use-keyboard-navigation';
 import { ClipboardEntry } from '@/types/clipboard';
+import { ExternalCommand, Transform } from '@/types/transforms';
 import clipboardService from '@/lib/clipboard-service';
 import clipboardDatabase from '@/lib/db';
 import Logger from '@/util/logger';
-import {
-  DEFAULT_KEYBOARD_NAVIGATION,
-  KeyboardNavigationSettings,
-  SETTING_KEYS,
-} from '@/types/settings';
 import { safeInvoke } from '@/lib/utils';
-import { DEFAULT_SHORTCUTS, type ShortcutDefinition } from '@/types/shortcuts';
+import { type KeyBinding } from '@/types/settings';
+
+const SNIPPET_NAME_LENGTH = 40;
 
 export const useClipboardActions = () => {
   const { state, dispatch } = useClipboardContext();
   const { selectedClipboardEntry } = state;
   const queryClient = useQueryClient();
 
-  const { value: settings } = useSetting<KeyboardNavigationSettings>(
-    SETTING_KEYS.KEYBOARD_NAVIGATION,
-    DEFAULT_KEYBOARD_NAVIGATION
-  );
+  const { value: settings } = useKeyboardNavigation();
 
   const invalidateClipboard = async () => {
     await queryClient.invalidateQueries({ queryKey: ['clipboardEntries'] });
+    // Lets the tray menu list the latest entries.
+    await emit('history-changed');
   };
 
   const copyEntry = async (entry: ClipboardEntry) => {
@@ -51,6 +49,118 @@ export const useClipboardActions = () => {
     await clipboardService.pasteEntry(entry);
   };
 
+  const pastePlainEntry = async (entry: ClipboardEntry) => {
+    await clipboardService.pastePlainEntry(entry);
+  };
+
+  const typeOutEntry = async (entry: ClipboardEntry) => {
+    try {
+      await clipboardService.typeOutEntry(entry);
+    } catch (error) {
+      Logger.error(`Failed to type out entry with ID ${entry.id}:`, error);
+    }
+  };
+
+  const pasteTransformedEntry = async (entry: ClipboardEntry, transform: Transform) => {
+    try {
+      await clipboardService.pasteTransformedEntry(entry, transform);
+    } catch (error) {
+      Logger.error(`Failed to paste entry with ID ${entry.id} transformed:`, error);
+    }
+  };
+
+  const saveTransformedEntry = async (entry: ClipboardEntry, transform: Transform) => {
+    try {
+      await clipboardService.saveTransformedEntry(entry, transform);
+      await invalidateClipboard();
+    } catch (error) {
+      Logger.error(`Failed to save transformed copy of entry with ID ${entry.id}:`, error);
+    }
+  };
+
+  const pasteScriptTransformedEntry = async (entry: ClipboardEntry, name: string) => {
+    try {
+      await clipboardService.pasteScriptTransformedEntry(entry, name);
+    } catch (error) {
+      Logger.error(`Failed to paste entry with ID ${entry.id} through script ${name}:`, error);
+    }
+  };
+
+  const saveScriptTransformedEntry = async (entry: ClipboardEntry, name: string) => {
+    try {
+      await clipboardService.saveScriptTransformedEntry(entry, name);
+      await invalidateClipboard();
+    } catch (error) {
+      Logger.error(`Failed to save entry with ID ${entry.id} through script ${name}:`, error);
+    }
+  };
+
+  const pasteCommandOutput = async (entry: ClipboardEntry, command: ExternalCommand) => {
+    try {
+      await clipboardService.pasteCommandOutput(entry, command.name);
+    } catch (error) {
+      Logger.error(`Failed to paste output of ${command.name} for entry ${entry.id}:`, error);
+    }
+  };
+
+  const saveCommandOutput = async (entry: ClipboardEntry, command: ExternalCommand) => {
+    try {
+      await clipboardService.saveCommandOutput(entry, command.name);
+      await invalidateClipboard();
+    } catch (error) {
+      Logger.error(`Failed to save output of ${command.name} for entry ${entry.id}:`, error);
+    }
+  };
+
+  const editEntryExternally = async (entry: ClipboardEntry) => {
+    try {
+      await clipboardService.editEntryExternally(entry);
+    } catch (error) {
+      Logger.error(`Failed to open entry with ID ${entry.id} in an external editor:`, error);
+    }
+  };
+
+  const pinEntry = async (entry: ClipboardEntry) => {
+    try {
+      await clipboardService.pinEntry(entry);
+    } catch (error) {
+      Logger.error(`Failed to pin entry with ID ${entry.id}:`, error);
+    }
+  };
+
+  const toggleEntryChecked = (id: number) => {
+    dispatch({ type: 'TOGGLE_CHECKED_ENTRY', payload: id });
+  };
+
+  const pasteCheckedEntries = async () => {
+    try {
+      await clipboardService.pasteJoinedEntries(state.checkedEntryIds);
+      dispatch({ type: 'CLEAR_CHECKED_ENTRIES' });
+    } catch (error) {
+      Logger.error('Failed to paste selected entries:', error);
+    }
+  };
+
+  const copyCheckedEntries = async () => {
+    try {
+      await clipboardService.copyJoinedEntries(state.checkedEntryIds);
+      dispatch({ type: 'CLEAR_CHECKED_ENTRIES' });
+      await invalidateClipboard();
+    } catch (error) {
+      Logger.error('Failed to copy selected entries:', error);
+    }
+  };
+
+  const saveAsSnippet = async (entry: ClipboardEntry) => {
+    const name = entry.content.trim().split('\n')[0].slice(0, SNIPPET_NAME_LENGTH);
+    try {
+      await clipboardDatabase.saveSnippet(name, entry.content);
+      await queryClient.invalidateQueries({ queryKey: ['snippets'] });
+    } catch (error) {
+      Logger.error(`Failed to save entry with ID ${entry.id} as a snippet:`, error);
+    }
+  };
+
   const toggleEntryFavorite = async (id: number, event?: MouseEvent) => {
     event?.stopPropagation();
     try {
@@ -75,36 +185,19 @@ export const useClipboardActions = () => {
     dispatch({ type: 'TOGGLE_FAVORITES_ONLY' });
   };
 
-  const applyShortcut = async (shortcut: ShortcutDefinition) => {
-    await safeInvoke('change_shortcut', {
-      modifiers: shortcut.modifiers,
-      key: shortcut.key,
-    });
-    dispatch({ type: 'SET_SHORTCUT', payload: shortcut });
-  };
-
-  const updateShortcut = async (shortcut: ShortcutDefinition) => {
+  /** The backend binds the toggle shortcut and saves it in the settings store. */
+  const updateShortcut = async (shortcut: KeyBinding) => {
     try {
-      await clipboardDatabase.setSetting(SETTING_KEYS.TOGGLE_SHORTCUT, shortcut);
-      await applyShortcut(shortcut);
+      await safeInvoke('change_shortcut', {
+        modifiers: shortcut.modifiers,
+        key: shortcut.key,
+      });
     } catch (error) {
       Logger.error('Failed to update shortcut:', error);
       throw error;
     }
   };
 
-  const initializeShortcut = async () => {
-    try {
-      const savedShortcut = await clipboardDatabase.getSetting<ShortcutDefinition>(
-        SETTING_KEYS.TOGGLE_SHORTCUT,
-        DEFAULT_SHORTCUTS.launch
-      );
-      await applyShortcut(savedShortcut);
-    } catch (error) {
-      Logger.error('Failed to initialize shortcut:', error);
-    }
-  };
-
   useKeyboardShortcut(
     settings.shortcuts.pasteEntry.key,
     () => selectedClipboardEntry && pasteEntry(selectedClipboardEntry),
@@ -114,6 +207,17 @@ export const useClipboardActions = () => {
     }
   );
 
+  const pastePlainShortcut = settings.shortcuts.pastePlainEntry;
+
+  useKeyboardShortcut(
+    pastePlainShortcut.key,
+    () => selectedClipboardEntry && pastePlainEntry(selectedClipboardEntry),
+    {
+      modifiers: pastePlainShortcut.modifiers,
+      enabled: !!selectedClipboardEntry,
+    }
+  );
+
   useKeyboardShortcut(
     settings.shortcuts.copyEntry.key,
     () => selectedClipboardEntry && copyEntry(selectedClipboardEntry),
@@ -146,10 +250,23 @@ export const useClipboardActions = () => {
     copyEntry,
     selectOrCopyEntry,
     pasteEntry,
+    pastePlainEntry,
+    typeOutEntry,
+    pasteTransformedEntry,
+    saveTransformedEntry,
+    pasteScriptTransformedEntry,
+    saveScriptTransformedEntry,
+    pasteCommandOutput,
+    saveCommandOutput,
+    editEntryExternally,
+    pinEntry,
+    toggleEntryChecked,
+    pasteCheckedEntries,
+    copyCheckedEntries,
+    saveAsSnippet,
     toggleEntryFavorite,
     deleteEntry,
     toggleFavoritesFilter,
-    initializeShortcut,
     updateShortcut,
   };
 };
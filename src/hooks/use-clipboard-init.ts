@@ -1,17 +1,17 @@
 import { useEffect } from 'react';
+import { listen } from '@tauri-apps/api/event';
 
 import clipboardService from '@/lib/clipboard-service';
-import clipboardDatabase from '@/lib/db';
 import Logger from '@/util/logger';
 import { useClipboardActions } from '@/hooks/use-clipboard-actions';
+import { EditedEntry } from '@/types/clipboard';
 
 export default function useClipboardInit() {
-  const { initializeShortcut, invalidateClipboard } = useClipboardActions();
+  const { invalidateClipboard } = useClipboardActions();
 
   useEffect(() => {
     const initialize = async () => {
       try {
-        await initializeShortcut();
         await clipboardService.startMonitoring();
       } catch (error) {
         Logger.error('Failed to initialize clipboard context:', error);
@@ -21,7 +21,22 @@ export default function useClipboardInit() {
     initialize();
     clipboardService.addEventListener('update', invalidateClipboard);
 
+    // Saves made in an external editor come back as revised entries.
+    const unlistenEdits = listen<EditedEntry>('entry-edited', async (event) => {
+      try {
+        await clipboardService.saveEditedEntry(event.payload);
+        await invalidateClipboard();
+      } catch (error) {
+        Logger.error(`Failed to save edits to entry ${event.payload.entryId}:`, error);
+      }
+    });
+
+    // History can be cleared from the settings window, or pruned by the backend.
+    const unlistenCleared = listen('history-cleared', invalidateClipboard);
+
     return () => {
+      unlistenEdits.then((unlisten) => unlisten());
+      unlistenCleared.then((unlisten) => unlisten());
       clipboardService.removeEventListener('update', invalidateClipboard);
       clipboardService.stopMonitoring();
       clipboardDatabase.close();
@@ -4,15 +4,10 @@ import { useInfiniteQuery } from '@tanstack/react-query';
 
 import { useClipboardContext } from '@/clipboard-context';
 import { useKeyboardShortcut } from '@/context/keyboard-context';
-import { useSetting } from '@/hooks/use-setting';
+import { useKeyboardNavigation } from '@/hooks/use-keyboard-navigation';
 import clipboardDatabase from '@/lib/db';
 import { ClipboardEntry } from '@/types/clipboard';
 import useDebounce from '@/hooks/use-debounce';
-import {
-  DEFAULT_KEYBOARD_NAVIGATION,
-  KeyboardNavigationSettings,
-  SETTING_KEYS,
-} from '@/types/settings';
 
 const BATCH_SIZE = 20;
 const ESTIMATE_SIZE = () => 50;
@@ -21,10 +16,7 @@ const OVERSCAN = 10;
 type PageParam = { id: number; cursorValue: string | number } | undefined;
 
 export default function useClipboardVirtualizer() {
-  const { value: settings } = useSetting<KeyboardNavigationSettings>(
-    SETTING_KEYS.KEYBOARD_NAVIGATION,
-    DEFAULT_KEYBOARD_NAVIGATION
-  );
+  const { value: settings } = useKeyboardNavigation();
   const { state, dispatch } = useClipboardContext();
   const {
     selectedClipboardEntry,
@@ -0,0 +1,34 @@
+import { useEffect } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+
+import { safeInvoke } from '@/lib/utils';
+import type { ConfigFileStatus } from '@/types/settings';
+
+const CONFIG_FILE_QUERY_KEY = ['configFile'];
+
+/** The config file's status, updated whenever the backend reloads it. */
+export function useConfigFile() {
+  const queryClient = useQueryClient();
+  const { data: status } = useQuery({
+    queryKey: CONFIG_FILE_QUERY_KEY,
+    queryFn: () => safeInvoke<ConfigFileStatus>('get_config_status'),
+    staleTime: Infinity,
+  });
+
+  useEffect(() => {
+    const unlisten = listen<ConfigFileStatus>('config-changed', (event) => {
+      queryClient.setQueryData(CONFIG_FILE_QUERY_KEY, event.payload);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [queryClient]);
+
+  return {
+    status,
+    transforms: status?.transforms ?? [],
+    openConfigFile: () => safeInvoke('open_config_file'),
+  };
+}
@@ -0,0 +1,24 @@
+import { useAppSetting } from '@/hooks/use-app-setting';
+import { DEFAULT_SHORTCUTS } from '@/types/shortcuts';
+import { type KeyboardNavigationSettings } from '@/types/settings';
+
+/** The panel's in-window shortcuts, with defaults for the ones never changed. */
+export function useKeyboardNavigation(delay: number = 0) {
+  const { value: saved, setValue, isLoaded } = useAppSetting('keyboardNavigation', delay);
+
+  const value: KeyboardNavigationSettings = {
+    ...saved,
+    shortcuts: Object.fromEntries(
+      Object.entries(DEFAULT_SHORTCUTS).map(([name, shortcut]) => [
+        name,
+        { ...shortcut, ...saved.shortcuts[name] },
+      ])
+    ),
+  };
+
+  const setShortcut = (name: string, modifiers: string[], key: string) => {
+    setValue({ ...saved, shortcuts: { ...saved.shortcuts, [name]: { modifiers, key } } });
+  };
+
+  return { value, isLoaded, setShortcut };
+}
@@ -1,19 +1,13 @@
 import { useCallback } from 'react';
 
-import { useSetting } from '@/hooks/use-setting';
-import { DEFAULT_PANEL_LAYOUT, PanelLayout, SETTING_KEYS } from '@/types/settings';
+import { useAppSetting } from '@/hooks/use-app-setting';
+import { PanelLayout } from '@/types/settings';
 
 export function usePanelLayout() {
-  const leftPanel = useSetting(SETTING_KEYS.LEFT_PANEL, DEFAULT_PANEL_LAYOUT.leftPanelSize, 300);
-  const rightPanel = useSetting(SETTING_KEYS.RIGHT_PANEL, DEFAULT_PANEL_LAYOUT.rightPanelSize, 300);
-  const rightPanelCollapsed = useSetting(
-    SETTING_KEYS.RIGHT_PANEL_COLLAPSED,
-    DEFAULT_PANEL_LAYOUT.isRightPanelCollapsed
-  );
-  const filterSidebarCollapsed = useSetting(
-    SETTING_KEYS.FILTER_SIDEBAR_COLLAPSED,
-    DEFAULT_PANEL_LAYOUT.isFilterSidebarCollapsed
-  );
+  const leftPanel = useAppSetting('leftPanelSize', 300);
+  const rightPanel = useAppSetting('rightPanelSize', 300);
+  const rightPanelCollapsed = useAppSetting('rightPanelCollapsed');
+  const filterSidebarCollapsed = useAppSetting('filterSidebarCollapsed');
 
   const isLoaded =
     leftPanel.isLoaded &&
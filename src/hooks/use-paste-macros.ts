@@ -0,0 +1,39 @@
+import { safeInvoke } from '@/lib/utils';
+import { getAppSettings, updateAppSettings } from '@/lib/app-settings';
+import Logger from '@/util/logger';
+import { useAppSetting } from '@/hooks/use-app-setting';
+import { type PasteMacro } from '@/types/macros';
+
+/** Macros live in the settings store, where the backend runs them from. */
+export function usePasteMacros() {
+  const { value: macros } = useAppSetting('pasteMacros');
+
+  const runMacro = async (id: number) => {
+    try {
+      const { restoreClipboard } = await getAppSettings();
+      await safeInvoke('run_macro', { id, restoreClipboard });
+    } catch (error) {
+      Logger.error('Failed to run paste macro:', error);
+    }
+  };
+
+  /** Adds or replaces a macro. Throws the backend's validation error. */
+  const saveMacro = async (pasteMacro: PasteMacro) => {
+    const exists = macros.some((other) => other.id === pasteMacro.id);
+    await updateAppSettings({
+      pasteMacros: exists
+        ? macros.map((other) => (other.id === pasteMacro.id ? pasteMacro : other))
+        : [...macros, pasteMacro],
+    });
+  };
+
+  /** Removes a macro along with its global shortcut. */
+  const deleteMacro = async (id: number) => {
+    await safeInvoke('set_global_shortcut', { action: { run_macro: id }, accelerator: null });
+    await updateAppSettings({ pasteMacros: macros.filter((other) => other.id !== id) });
+  };
+
+  const nextMacroId = () => Math.max(0, ...macros.map((pasteMacro) => pasteMacro.id)) + 1;
+
+  return { macros, runMacro, saveMacro, deleteMacro, nextMacroId };
+}
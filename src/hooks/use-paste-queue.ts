@@ -0,0 +1,45 @@
+import { useEffect, useState } from 'react';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+import { safeInvoke } from '@/lib/utils';
+import Logger from '@/util/logger';
+import { ClipboardEntry } from '@/types/clipboard';
+
+export function usePasteQueue() {
+  const [queue, setQueue] = useState<ClipboardEntry[]>([]);
+
+  useEffect(() => {
+    let unlisten: UnlistenFn | undefined;
+
+    safeInvoke<ClipboardEntry[]>('get_paste_queue').then(setQueue);
+    listen<ClipboardEntry[]>('paste-queue-changed', (event) => {
+      setQueue(event.payload);
+    }).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  const enqueueEntries = async (entries: ClipboardEntry[]) => {
+    try {
+      await safeInvoke('enqueue_entries', { entries });
+    } catch (error) {
+      Logger.error('Failed to add entries to the paste queue:', error);
+    }
+  };
+
+  const clearQueue = async () => {
+    try {
+      await safeInvoke('clear_paste_queue');
+    } catch (error) {
+      Logger.error('Failed to clear the paste queue:', error);
+    }
+  };
+
+  return { queue, enqueueEntries, clearQueue };
+}
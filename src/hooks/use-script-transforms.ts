@@ -0,0 +1,15 @@
+import { useQuery } from '@tanstack/react-query';
+
+import { safeInvoke } from '@/lib/utils';
+import { ScriptTransform } from '@/types/transforms';
+
+export function useScriptTransforms() {
+  const { data: scripts = [] } = useQuery({
+    queryKey: ['scriptTransforms'],
+    queryFn: () => safeInvoke<ScriptTransform[]>('list_script_transforms'),
+    // Scripts are edited outside Nabu; refetched when the panel regains focus.
+    staleTime: 30_000,
+  });
+
+  return { scripts };
+}
@@ -0,0 +1,44 @@
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+
+import clipboardDatabase from '@/lib/db';
+import clipboardService from '@/lib/clipboard-service';
+import { safeInvoke } from '@/lib/utils';
+import Logger from '@/util/logger';
+import useDebounce from '@/hooks/use-debounce';
+import { Snippet } from '@/types/snippets';
+
+export function useSnippets(searchQuery: string, limit?: number) {
+  const queryClient = useQueryClient();
+  const debouncedSearchQuery = useDebounce(searchQuery, 300);
+
+  const { data: snippets = [] } = useQuery({
+    queryKey: ['snippets', { debouncedSearchQuery, limit }],
+    queryFn: () => clipboardDatabase.getSnippets(debouncedSearchQuery, limit),
+  });
+
+  const invalidateSnippets = async () => {
+    await queryClient.invalidateQueries({ queryKey: ['snippets'] });
+  };
+
+  const deleteSnippet = async (id: number) => {
+    try {
+      await clipboardDatabase.deleteSnippet(id);
+      await invalidateSnippets();
+    } catch (error) {
+      Logger.error(`Failed to delete snippet with ID ${id}:`, error);
+    }
+  };
+
+  const getSnippetInputs = (snippet: Snippet) =>
+    safeInvoke<string[]>('snippet_inputs', { template: snippet.template });
+
+  const pasteSnippet = async (snippet: Snippet, inputs?: Record<string, string>) => {
+    try {
+      await clipboardService.pasteSnippet(snippet.template, inputs);
+    } catch (error) {
+      Logger.error(`Failed to paste snippet with ID ${snippet.id}:`, error);
+    }
+  };
+
+  return { snippets, deleteSnippet, getSnippetInputs, pasteSnippet };
+}
@@ -0,0 +1,11 @@
+import { safeInvoke } from '@/lib/utils';
+import type { AppSettings } from '@/types/settings';
+
+export function getAppSettings() {
+  return safeInvoke<AppSettings>('get_settings');
+}
+
+/** Saves the given settings; the backend validates them and emits `settings-changed`. */
+export function updateAppSettings(patch: Partial<AppSettings>) {
+  return safeInvoke<AppSettings>('update_settings', { patch });
+}
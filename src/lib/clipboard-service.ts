@@ -1,16 +1,5 @@
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import {
-  hasFiles,
-  hasHTML,
-  hasImage,
-  hasRTF,
-  hasText,
-  onSomethingUpdate,
-  readFiles,
-  readHtml,
-  readImageBase64,
-  readRtf,
-  readText,
-  startListening,
   writeFiles,
   writeHtmlAndText,
   writeImageBase64,
@@ -18,24 +7,15 @@ import {
   writeText,
 } from 'tauri-plugin-clipboard-api';
 
+import { getAppSettings } from '@/lib/app-settings';
 import { safeInvoke } from '@/lib/utils';
 import clipboardDatabase from '@/lib/db';
 import Logger from '@/util/logger';
-import { ClipboardCaptureOptions, ClipboardContentType, ClipboardEntry } from '@/types/clipboard';
-import { detectSpecialType, getImageMetadata, parseWindowTitle } from '@/util/clipboard-parser';
-
-export const DEFAULT_CAPTURE_OPTIONS: ClipboardCaptureOptions = {
-  text: true,
-  html: true,
-  rtf: true,
-  image: true,
-  files: true,
-};
+import { ClipboardEntry, EditedEntry } from '@/types/clipboard';
+import { Transform } from '@/types/transforms';
 
 class ClipboardService {
-  private unlistenFn?: () => Promise<void>;
-  private captureOptions = DEFAULT_CAPTURE_OPTIONS;
-  private lastCapturedContent = { content: '', contentType: '' };
+  private unlistenFn?: UnlistenFn;
   private eventTarget = new EventTarget();
 
   constructor() {
@@ -60,170 +40,24 @@ class ClipboardService {
     this.eventTarget.removeEventListener(type, listener, options);
   }
 
-  async startMonitoring(options: Partial<ClipboardCaptureOptions> = {}) {
+  /** History is recorded by the backend, or by `nabu --daemon`; this only follows it. */
+  async startMonitoring() {
     if (this.unlistenFn) {
       await this.stopMonitoring();
     }
 
-    try {
-      this.captureOptions = { ...DEFAULT_CAPTURE_OPTIONS, ...options };
-      this.unlistenFn = await startListening(this.captureOptions);
-
-      await this.captureCurrentClipboard();
-      await onSomethingUpdate(this.handleClipboardUpdate.bind(this));
-    } catch (err) {
-      Logger.error('Failed to start clipboard monitoring', err);
-      throw err;
-    }
+    this.unlistenFn = await listen('entry-recorded', () => {
+      this.eventTarget.dispatchEvent(new Event('update'));
+    });
   }
 
   async stopMonitoring() {
     if (this.unlistenFn) {
-      await this.unlistenFn();
+      this.unlistenFn();
       this.unlistenFn = undefined;
     }
   }
 
-  async captureCurrentClipboard() {
-    try {
-      const contentTypeChecks = [
-        { type: 'image', enabled: this.captureOptions.image, checkFn: hasImage },
-        { type: 'html', enabled: this.captureOptions.html, checkFn: hasHTML },
-        { type: 'rtf', enabled: this.captureOptions.rtf, checkFn: hasRTF },
-        { type: 'file', enabled: this.captureOptions.files, checkFn: hasFiles },
-        { type: 'text', enabled: this.captureOptions.text, checkFn: hasText },
-      ] as const;
-
-      for (const { type, enabled, checkFn } of contentTypeChecks) {
-        if (enabled && (await checkFn())) {
-          await this.captureContent(type);
-          break;
-        }
-      }
-
-      this.eventTarget.dispatchEvent(new Event('update'));
-    } catch (error) {
-      Logger.error('Error capturing clipboard:', error);
-      this.eventTarget.dispatchEvent(new Event('error'));
-    }
-  }
-
-  private async handleClipboardUpdate(types: any) {
-    try {
-      let updated = false;
-
-      const contentTypeChecks = [
-        { type: 'image', enabled: this.captureOptions.image, hasContent: types.image },
-        { type: 'html', enabled: this.captureOptions.html, hasContent: types.html },
-        { type: 'rtf', enabled: this.captureOptions.rtf, hasContent: types.rtf },
-        { type: 'file', enabled: this.captureOptions.files, hasContent: types.files },
-        { type: 'text', enabled: this.captureOptions.text, hasContent: types.text },
-      ] as const;
-
-      for (const { type, enabled, hasContent } of contentTypeChecks) {
-        if (enabled && hasContent) {
-          updated = await this.captureContent(type);
-          break;
-        }
-      }
-
-      if (updated) this.eventTarget.dispatchEvent(new Event('update'));
-    } catch (error) {
-      Logger.error('Error handling clipboard update:', error);
-      this.eventTarget.dispatchEvent(new Event('error'));
-    }
-  }
-
-  private async captureContent(contentType: ClipboardContentType): Promise<boolean> {
-    try {
-      let currentContentType = contentType;
-      let content = '';
-      let preview = '';
-      let sourceUrl = '';
-      let metadata = '';
-      let plainTextContent = '';
-
-      switch (currentContentType) {
-        case 'image':
-          content = (await readImageBase64()) || '';
-          if (content) {
-            const { width, height, sizeKB } = await getImageMetadata(content);
-            preview = `Image [${width}x${height}]`;
-            metadata = `[${sizeKB} KB]`;
-          }
-          break;
-        case 'html':
-          content = (await readHtml()) || '';
-          plainTextContent = (await readText()) || '';
-          preview = plainTextContent;
-          sourceUrl = (await safeInvoke('get_clipboard_source_url')) || '';
-          break;
-        case 'rtf':
-          content = (await readRtf()) || '';
-          plainTextContent = (await readText()) || '';
-          preview = plainTextContent;
-          break;
-        case 'file':
-          const files = await readFiles();
-          content = files.join('\n');
-          preview = content;
-          break;
-        case 'text':
-          content = (await readText()) || '';
-          plainTextContent = content;
-          preview = content;
-          break;
-      }
-
-      if (['text', 'html', 'rtf'].includes(currentContentType) && plainTextContent) {
-        const specialType = detectSpecialType(plainTextContent);
-        if (specialType.type !== 'text') {
-          currentContentType = specialType.type;
-        }
-      }
-
-      if (
-        !content ||
-        (this.lastCapturedContent.content === content &&
-          this.lastCapturedContent.contentType === currentContentType)
-      ) {
-        return false;
-      }
-
-      this.lastCapturedContent = { content, contentType: currentContentType };
-      let windowTitle = '';
-      let windowSource = '';
-      try {
-        const windowInfo = parseWindowTitle(
-          (await safeInvoke('get_foreground_window_title')) || ''
-        );
-        windowTitle = windowInfo.title;
-        windowSource = windowInfo.source;
-      } catch (e) {
-        windowTitle = '';
-        windowSource = '';
-      }
-
-      const appMetadata = `[${windowTitle}] (${windowSource})`;
-      if (contentType === 'image') {
-        metadata = `${appMetadata} ${metadata}`;
-      } else {
-        metadata = appMetadata;
-      }
-
-      return await clipboardDatabase.saveClipboardEntry(
-        content,
-        currentContentType,
-        preview,
-        metadata,
-        sourceUrl
-      );
-    } catch (error) {
-      Logger.error(`Error capturing ${contentType}:`, error);
-      return false;
-    }
-  }
-
   async copyToClipboard(entry: ClipboardEntry) {
     try {
       switch (entry.contentType) {
@@ -255,10 +89,108 @@ class ClipboardService {
   }
 
   async pasteEntry(entry: ClipboardEntry) {
-    await this.copyToClipboard(entry);
-    await safeInvoke('paste');
+    const restoreClipboard = await this.shouldRestoreClipboard();
+    if (restoreClipboard) await safeInvoke('snapshot_clipboard');
+
+    try {
+      await this.copyToClipboard(entry);
+    } catch (error) {
+      if (restoreClipboard) await safeInvoke('abort_paste');
+      throw error;
+    }
+    await safeInvoke('paste', { restoreClipboard });
     Logger.debug('Pasted item in previous application');
   }
+
+  async pastePlainEntry(entry: ClipboardEntry) {
+    const restoreClipboard = await this.shouldRestoreClipboard();
+    await safeInvoke('paste_plain', { entry, restoreClipboard });
+    Logger.debug('Pasted item as plain text in previous application');
+  }
+
+  /** Types the entry out as plain text; Esc or the tray's Stop Typing ends it early. */
+  async typeOutEntry(entry: ClipboardEntry) {
+    await safeInvoke('paste_typed', { entry });
+    Logger.debug('Typed item out in previous application');
+  }
+
+  async pasteJoinedEntries(ids: number[]) {
+    const restoreClipboard = await this.shouldRestoreClipboard();
+    const separator = await this.joinSeparator();
+    await safeInvoke('paste_joined', { ids, separator, restoreClipboard });
+    Logger.debug(`Pasted ${ids.length} joined items in previous application`);
+  }
+
+  async copyJoinedEntries(ids: number[]) {
+    const separator = await this.joinSeparator();
+    await safeInvoke('copy_joined', { ids, separator });
+    Logger.debug(`Copied ${ids.length} joined items to clipboard`);
+  }
+
+  async pasteTransformedEntry(entry: ClipboardEntry, transform: Transform) {
+    const restoreClipboard = await this.shouldRestoreClipboard();
+    await safeInvoke('paste_transformed', { entry, transform, restoreClipboard });
+    Logger.debug(`Pasted item with ${transform} applied in previous application`);
+  }
+
+  async saveTransformedEntry(entry: ClipboardEntry, transform: Transform) {
+    const text = await safeInvoke<string>('transform_entry', { entry, transform });
+    return clipboardDatabase.saveClipboardEntry(text, 'text', text);
+  }
+
+  async pasteScriptTransformedEntry(entry: ClipboardEntry, name: string) {
+    const restoreClipboard = await this.shouldRestoreClipboard();
+    await safeInvoke('paste_script_transform', { name, entry, restoreClipboard });
+    Logger.debug(`Pasted item through script ${name} in previous application`);
+  }
+
+  async saveScriptTransformedEntry(entry: ClipboardEntry, name: string) {
+    const text = await safeInvoke<string>('run_script_transform', { name, entry });
+    return clipboardDatabase.saveClipboardEntry(text, 'text', text);
+  }
+
+  /** Commands are looked up by name in the backend, from nabu.toml or the settings. */
+  async pasteCommandOutput(entry: ClipboardEntry, name: string) {
+    const restoreClipboard = await this.shouldRestoreClipboard();
+    await safeInvoke('paste_external_command', { entry, name, restoreClipboard });
+    Logger.debug(`Pasted output of ${name} in previous application`);
+  }
+
+  async saveCommandOutput(entry: ClipboardEntry, name: string) {
+    const text = await safeInvoke<string>('run_external_command', { entry, name });
+    return clipboardDatabase.saveClipboardEntry(text, 'text', text);
+  }
+
+  /** The backend picks the editor: the setting, then $VISUAL or $EDITOR. */
+  async editEntryExternally(entry: ClipboardEntry) {
+    await safeInvoke('edit_entry_externally', { entry });
+  }
+
+  async pinEntry(entry: ClipboardEntry) {
+    await safeInvoke('pin_entry', { entry });
+  }
+
+  async saveEditedEntry(edited: EditedEntry) {
+    return clipboardDatabase.saveClipboardEntry(edited.content, edited.contentType, edited.preview);
+  }
+
+  async expandSnippet(template: string, inputs: Record<string, string> = {}) {
+    return safeInvoke<string>('expand_snippet', { template, inputs });
+  }
+
+  async pasteSnippet(template: string, inputs: Record<string, string> = {}) {
+    const restoreClipboard = await this.shouldRestoreClipboard();
+    await safeInvoke('paste_snippet', { template, inputs, restoreClipboard });
+    Logger.debug('Pasted snippet in previous application');
+  }
+
+  private async joinSeparator() {
+    return (await getAppSettings()).joinSeparator;
+  }
+
+  private async shouldRestoreClipboard() {
+    return (await getAppSettings()).restoreClipboard;
+  }
 }
 
 const clipboardService = new ClipboardService();
@@ -7,6 +7,7 @@ import {
   SortBy,
   SortDirection,
 } from '@/types/clipboard';
+import { Snippet } from '@/types/snippets';
 import Logger from '@/util/logger';
 
 class ClipboardDatabase {
@@ -44,6 +45,17 @@ class ClipboardDatabase {
                 )
             `);
 
+      await this.db.execute(`
+                CREATE TABLE IF NOT EXISTS snippets
+                (
+                    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name       TEXT NOT NULL,
+                    template   TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                )
+            `);
+
       this.isInitialized = true;
     } catch (err) {
       Logger.error('Failed to initialize database:', err);
@@ -240,50 +252,46 @@ class ClipboardDatabase {
     }));
   }
 
-  async getSetting<T>(key: string, defaultValue: T): Promise<T> {
+  async getSnippets(searchQuery?: string, limit: number = 50): Promise<Snippet[]> {
     await this.init();
-    if (!this.db) return defaultValue;
-
-    try {
-      const result = await this.db.select<{ value: string }[]>(
-        'SELECT value FROM settings WHERE key = $1',
-        [key]
-      );
+    if (!this.db) return [];
 
-      if (result.length === 0) return defaultValue;
+    const trimmedQuery = searchQuery?.trim();
+    const results = trimmedQuery
+      ? await this.db.select<any[]>(
+          'SELECT * FROM snippets WHERE name LIKE $1 OR template LIKE $1 ORDER BY name LIMIT $2',
+          [`%${trimmedQuery}%`, limit]
+        )
+      : await this.db.select<any[]>('SELECT * FROM snippets ORDER BY name LIMIT $1', [limit]);
+
+    return results.map((dbSnippet) => ({
+      id: dbSnippet.id,
+      name: dbSnippet.name,
+      template: dbSnippet.template,
+      createdAt: dbSnippet.created_at,
+      updatedAt: dbSnippet.updated_at,
+    }));
+  }
 
-      const value = result[0].value;
+  async saveSnippet(name: string, template: string): Promise<boolean> {
+    await this.init();
+    if (!this.db || !name.trim() || !template) return false;
 
-      if (typeof defaultValue === 'number') return Number(value) as T;
-      if (typeof defaultValue === 'boolean') return (value === 'true') as T;
-      if (typeof defaultValue === 'object') return JSON.parse(value) as T;
+    const timestamp = new Date().toISOString();
+    const result = await this.db.execute(
+      'INSERT INTO snippets (name, template, created_at, updated_at) VALUES ($1, $2, $3, $3)',
+      [name.trim(), template, timestamp]
+    );
 
-      return value as T;
-    } catch (err) {
-      Logger.error(`Failed to get setting: ${key}`, err);
-      return defaultValue;
-    }
+    return result.rowsAffected > 0;
   }
 
-  async setSetting<T>(key: string, value: T): Promise<boolean> {
+  async deleteSnippet(id: number): Promise<boolean> {
     await this.init();
-    if (!this.db) return false;
-
-    try {
-      const stringValue = typeof value === 'object' ? JSON.stringify(value) : String(value);
-
-      const result = await this.db.execute(
-        `INSERT INTO settings (key, value)
-                 VALUES ($1, $2)
-                 ON CONFLICT(key) DO UPDATE SET value = $2`,
-        [key, stringValue]
-      );
+    if (!this.db || !id) return false;
 
-      return result.rowsAffected > 0;
-    } catch (err) {
-      Logger.error(`Failed to set setting: ${key}`, err);
-      return false;
-    }
+    const result = await this.db.execute('DELETE FROM snippets WHERE id = $1', [id]);
+    return result.rowsAffected > 0;
   }
 
   async close() {
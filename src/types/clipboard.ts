@@ -21,12 +21,12 @@ export interface ClipboardEntry {
   sourceUrl?: string;
 }
 
-export interface ClipboardCaptureOptions {
-  text: boolean;
-  html: boolean;
-  rtf: boolean;
-  image: boolean;
-  files: boolean;
+/** Payload of the backend's `entry-edited` event. */
+export interface EditedEntry {
+  entryId: number;
+  content: string;
+  contentType: ClipboardContentType;
+  preview: string;
 }
 
 export type SortDirection = 'ASC' | 'DESC';
@@ -0,0 +1,18 @@
+import { ClipboardEntry } from '@/types/clipboard';
+
+/** The part of an entry a paste step keeps; steps written in the settings window use id 0. */
+export type MacroEntry = Pick<ClipboardEntry, 'id' | 'content' | 'contentType' | 'preview'>;
+
+export type MacroStep =
+  | { type: 'paste'; entry: MacroEntry }
+  | { type: 'key'; key: string; modifiers?: string[] }
+  | { type: 'wait'; ms: number };
+
+export interface PasteMacro {
+  id: number;
+  name: string;
+  steps: MacroStep[];
+  stepDelayMs: number;
+}
+
+export const DEFAULT_MACRO_STEP_DELAY_MS = 150;
@@ -1,4 +1,11 @@
-import { DEFAULT_SHORTCUTS, type ShortcutDefinition } from '@/types/shortcuts';
+import { type PasteMacro } from '@/types/macros';
+import {
+  DEFAULT_SHORTCUTS,
+  type GlobalShortcut,
+  type ShortcutDefinition,
+} from '@/types/shortcuts';
+import { type ColorTheme, DEFAULT_THEME } from '@/types/theme';
+import { type ExternalCommand } from '@/types/transforms';
 
 export interface PanelLayout {
   leftPanelSize: number;
@@ -13,6 +20,18 @@ export interface KeyboardNavigationSettings {
   prefetchThreshold: number;
 }
 
+/** A key with modifiers, as saved in the settings store. */
+export type KeyBinding = Pick<ShortcutDefinition, 'modifiers' | 'key'>;
+
+/** The stored form of {@link KeyboardNavigationSettings}: only changed shortcuts. */
+export interface SavedKeyboardNavigation {
+  shortcuts: Record<string, KeyBinding>;
+  pageSize: number;
+  prefetchThreshold: number;
+}
+
+export type ThemeMode = 'dark' | 'light' | 'system';
+
 export const DEFAULT_PANEL_LAYOUT: PanelLayout = {
   leftPanelSize: 40,
   rightPanelSize: 60,
@@ -26,14 +45,95 @@ export const DEFAULT_KEYBOARD_NAVIGATION: KeyboardNavigationSettings = {
   prefetchThreshold: 3,
 };
 
-export const DEFAULT_AUTO_START = false;
+export const PANEL_PLACEMENTS = {
+  last_position: 'Where It Was Last',
+  center: 'Center of Main Monitor',
+  cursor_monitor: 'Center of Active Monitor',
+  at_cursor: 'At Mouse Cursor',
+} as const;
+
+export type PanelPlacement = keyof typeof PANEL_PLACEMENTS;
 
-export const SETTING_KEYS = {
-  LEFT_PANEL: 'left_panel',
-  RIGHT_PANEL: 'right_panel',
-  RIGHT_PANEL_COLLAPSED: 'right_panel_collapsed',
-  FILTER_SIDEBAR_COLLAPSED: 'filter_sidebar_open',
-  TOGGLE_SHORTCUT: 'toggle_shortcut',
-  AUTO_START: 'auto_start',
-  KEYBOARD_NAVIGATION: 'keyboard_navigation',
+/** Ctrl becomes Cmd on macOS; see `PasteStrategy` in the backend's `paste.rs`. */
+export const PASTE_STRATEGIES = {
+  ctrl_v: 'Ctrl+V',
+  ctrl_shift_v: 'Ctrl+Shift+V',
+  shift_insert: 'Shift+Insert',
+  type_text: 'Type Text',
 } as const;
+
+export type PasteStrategy = keyof typeof PASTE_STRATEGIES;
+
+/** Matches a process name or window class. */
+export interface PasteRule {
+  app: string;
+  strategy: PasteStrategy;
+}
+
+export const JOIN_SEPARATOR_PRESETS = {
+  newline: { label: 'Newline', value: '\n' },
+  comma: { label: 'Comma', value: ', ' },
+  tab: { label: 'Tab', value: '\t' },
+} as const;
+
+/** Settings owned by the backend's settings store; see `settings.rs`. */
+export interface AppSettings {
+  version: number;
+  autoStart: boolean;
+  hideOnBlur: boolean;
+  panelPlacement: PanelPlacement;
+  restoreClipboard: boolean;
+  joinSeparator: string;
+  typingDelayMs: number;
+  pasteRules: PasteRule[];
+  externalCommands: ExternalCommand[];
+  externalEditor: string;
+  toggleShortcut: KeyBinding;
+  globalShortcuts: GlobalShortcut[];
+  keyboardNavigation: SavedKeyboardNavigation;
+  themeMode: ThemeMode;
+  colorTheme: ColorTheme;
+  pasteMacros: PasteMacro[];
+  leftPanelSize: number;
+  rightPanelSize: number;
+  rightPanelCollapsed: boolean;
+  filterSidebarCollapsed: boolean;
+}
+
+/** Shown until the backend has answered; mirrors `Settings::default()`. */
+export const DEFAULT_APP_SETTINGS: AppSettings = {
+  version: 2,
+  autoStart: false,
+  hideOnBlur: true,
+  panelPlacement: 'last_position',
+  restoreClipboard: false,
+  joinSeparator: JOIN_SEPARATOR_PRESETS.newline.value,
+  typingDelayMs: 0,
+  pasteRules: [],
+  externalCommands: [],
+  externalEditor: '',
+  toggleShortcut: {
+    modifiers: DEFAULT_SHORTCUTS.launch.modifiers,
+    key: DEFAULT_SHORTCUTS.launch.key,
+  },
+  globalShortcuts: [],
+  keyboardNavigation: {
+    shortcuts: {},
+    pageSize: DEFAULT_KEYBOARD_NAVIGATION.pageSize,
+    prefetchThreshold: DEFAULT_KEYBOARD_NAVIGATION.prefetchThreshold,
+  },
+  themeMode: 'dark',
+  colorTheme: DEFAULT_THEME,
+  pasteMacros: [],
+  leftPanelSize: DEFAULT_PANEL_LAYOUT.leftPanelSize,
+  rightPanelSize: DEFAULT_PANEL_LAYOUT.rightPanelSize,
+  rightPanelCollapsed: DEFAULT_PANEL_LAYOUT.isRightPanelCollapsed,
+  filterSidebarCollapsed: false,
+};
+
+/** The state of `nabu.toml`, as reported by the backend's `config.rs`. */
+export interface ConfigFileStatus {
+  path: string;
+  error: string | null;
+  transforms: ExternalCommand[];
+}
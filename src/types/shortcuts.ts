@@ -4,6 +4,16 @@ export interface ShortcutDefinition {
   label: string;
 }
 
+/** An action a global shortcut runs; see `ShortcutAction` in `shortcuts.rs`. */
+export type ShortcutAction = 'paste_plain' | 'paste_next' | { run_macro: number };
+
+/** A global shortcut saved in the settings store. The accelerator is written as in
+ * `nabu.toml`, with a space between the two keys of a chord. */
+export interface GlobalShortcut {
+  action: ShortcutAction;
+  accelerator: string;
+}
+
 export const DEFAULT_SHORTCUTS = {
   launch: {
     modifiers: ['ctrl', 'shift'],
@@ -35,6 +45,11 @@ export const DEFAULT_SHORTCUTS = {
     key: 'Enter',
     label: 'Paste Entry',
   },
+  pastePlainEntry: {
+    modifiers: ['shift'],
+    key: 'Enter',
+    label: 'Paste as Plain Text',
+  },
   copyEntry: {
     modifiers: [],
     key: 'KeyC',
@@ -0,0 +1,7 @@
+export interface Snippet {
+  id: number;
+  name: string;
+  template: string;
+  createdAt: string;
+  updatedAt: string;
+}
@@ -0,0 +1,52 @@
+export type Transform =
+  | 'uppercase'
+  | 'lowercase'
+  | 'title_case'
+  | 'snake_case'
+  | 'camel_case'
+  | 'trim'
+  | 'collapse_whitespace'
+  | 'sort_lines'
+  | 'dedupe_lines'
+  | 'crlf_to_lf'
+  | 'lf_to_crlf'
+  | 'url_encode'
+  | 'url_decode'
+  | 'base64_encode'
+  | 'base64_decode'
+  | 'json_pretty'
+  | 'json_minify'
+  | 'html_escape';
+
+export const TRANSFORMS: { value: Transform; label: string }[] = [
+  { value: 'uppercase', label: 'UPPERCASE' },
+  { value: 'lowercase', label: 'lowercase' },
+  { value: 'title_case', label: 'Title Case' },
+  { value: 'snake_case', label: 'snake_case' },
+  { value: 'camel_case', label: 'camelCase' },
+  { value: 'trim', label: 'Trim' },
+  { value: 'collapse_whitespace', label: 'Collapse Whitespace' },
+  { value: 'sort_lines', label: 'Sort Lines' },
+  { value: 'dedupe_lines', label: 'Remove Duplicate Lines' },
+  { value: 'crlf_to_lf', label: 'CRLF → LF' },
+  { value: 'lf_to_crlf', label: 'LF → CRLF' },
+  { value: 'url_encode', label: 'URL Encode' },
+  { value: 'url_decode', label: 'URL Decode' },
+  { value: 'base64_encode', label: 'Base64 Encode' },
+  { value: 'base64_decode', label: 'Base64 Decode' },
+  { value: 'json_pretty', label: 'Pretty-print JSON' },
+  { value: 'json_minify', label: 'Minify JSON' },
+  { value: 'html_escape', label: 'HTML Escape' },
+];
+
+/** A user script from the config directory's `transforms` folder. */
+export interface ScriptTransform {
+  name: string;
+  description?: string;
+}
+
+/** A user-configured program that receives an entry on stdin. */
+export interface ExternalCommand {
+  name: string;
+  commandLine: string;
+}
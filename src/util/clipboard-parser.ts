@@ -1,48 +1,5 @@
 import { isMacOS } from '@/util/platform';
 
-export function parseWindowTitle(rawTitle: string): { title: string; source: string } {
-  if (!rawTitle) return { title: '', source: '' };
-
-  const parts = rawTitle.split(' - ');
-  let source = '';
-  let title = rawTitle;
-
-  if (parts.length > 1) {
-    source = parts[parts.length - 1].trim();
-
-    title = parts
-      .slice(0, -1)
-      .join(' - ')
-      .replace(/\s*and \d+ more pages\s*/i, '')
-      .replace(/\s*Personal\s*/i, '')
-      .replace(/\s*-\s*$/, '')
-      .trim();
-  }
-
-  return { title, source };
-}
-
-export async function getImageMetadata(base64: string): Promise<{
-  width: number;
-  height: number;
-  sizeKB: number;
-}> {
-  return new Promise((resolve) => {
-    const img = new Image();
-    img.onload = () => {
-      const paddingChars = base64.endsWith('==') ? 2 : base64.endsWith('=') ? 1 : 0;
-      const sizeInBytes = Math.floor((base64.length * 3) / 4 - paddingChars);
-      resolve({
-        width: img.naturalWidth,
-        height: img.naturalHeight,
-        sizeKB: Math.round(sizeInBytes / 1024),
-      });
-    };
-    img.onerror = () => resolve({ width: 0, height: 0, sizeKB: 0 });
-    img.src = `data:image/png;base64,${base64}`;
-  });
-}
-
 const REGEX = {
   URL: /^(https?:\/\/[^\s/$.?#].[^\s]*)$/i,
   EMAIL: